@@ -1 +1,12 @@
+pub mod agenda_dto;
+pub mod api_dto;
+pub mod board_dto;
+pub mod chart_dto;
+pub mod code_marker_dto;
+pub mod commit_dto;
+pub mod export_dto;
+pub mod graph_dto;
+pub mod import_dto;
 pub mod ticket_dto;
+pub mod timesheet_dto;
+pub mod workload_dto;
@@ -0,0 +1,9 @@
+use crate::dtos::ticket_dto::TicketDTO;
+use serde::{Deserialize, Serialize};
+
+// 期日の区分(Overdue/Today/This week/Later)ごとのチケット
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct AgendaGroupDTO {
+    pub title: String,
+    pub tickets: Vec<TicketDTO>,
+}
@@ -0,0 +1,44 @@
+use crate::dtos::ticket_dto::TicketDTO;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// API でチケットを作成・更新する内容。指定しなかった項目は変更せず、空文字列は値を取り除く
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TicketRequestDTO {
+    pub title: Option<String>,
+    // ラベル(Three, M など)または数値
+    pub level: Option<String>,
+    // Critical/High/Medium/Low
+    pub priority: Option<String>,
+    // メンバーのID。名簿のメンバーの名前やメールアドレスも受け付ける
+    pub assignee: Option<String>,
+    pub sprint: Option<String>,
+    pub parent: Option<String>,
+    pub blocked_by: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    // "2024-05-01" のほか "tomorrow" や "+3d" も受け付ける
+    pub due: Option<String>,
+    // daily, weekly:mon, monthly:15, cron:<expression>
+    pub recurrence: Option<String>,
+    pub external_id: Option<String>,
+    // カスタムフィールドの名前と値。指定したフィールドだけを変更する
+    pub fields: Option<BTreeMap<String, String>>,
+}
+
+// force の場合はブロックされていても作業中に移行できる
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransitionRequestDTO {
+    pub status: String,
+    #[serde(default)]
+    pub force: bool,
+}
+
+// 楽観的排他制御のための版(etag)を付けたチケット
+#[derive(Debug, Serialize)]
+pub struct VersionedTicketDTO {
+    #[serde(flatten)]
+    pub ticket: TicketDTO,
+    pub etag: String,
+}
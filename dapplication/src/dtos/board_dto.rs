@@ -0,0 +1,11 @@
+use crate::dtos::ticket_dto::TicketDTO;
+use serde::{Deserialize, Serialize};
+
+// ボードの列。ワークフローで宣言したステータスごとに1列
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct BoardColumnDTO {
+    pub status: String,
+    // todo/in-progress/done/canceled
+    pub category: String,
+    pub tickets: Vec<TicketDTO>,
+}
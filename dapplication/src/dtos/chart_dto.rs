@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+// グラフ描画用のデータ。x軸は集計開始からの経過日数
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ChartDTO {
+    pub window: String,
+    pub x_labels: Vec<String>,
+    pub x_max: f64,
+    pub burndown: Vec<(f64, f64)>,
+    pub velocity: Vec<(String, u64)>,
+    // 累積フロー図はステータスの分類ごとに下から done, in-progress, todo の順に積み上げた値
+    pub done_flow: Vec<(f64, f64)>,
+    pub in_progress_flow: Vec<(f64, f64)>,
+    pub todo_flow: Vec<(f64, f64)>,
+    // 担当者ごとの未完了ポイント
+    pub workload: Vec<(String, u64)>,
+}
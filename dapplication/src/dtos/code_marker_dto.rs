@@ -0,0 +1,9 @@
+use ddomain::services::code_markers::CodeMarker;
+use ddomain::value_objects::source_location::SourceLocation;
+
+// ソースコードで見つかった TODO などのコメント1件
+#[derive(Debug, Clone)]
+pub struct CodeMarkerDTO {
+    pub location: SourceLocation,
+    pub marker: CodeMarker,
+}
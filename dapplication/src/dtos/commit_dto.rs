@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// 履歴から読み込んだコミット
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct CommitDTO {
+    pub hash: String,
+    pub author: String,
+    pub date: DateTime<Utc>,
+    // 件名と本文を含むメッセージ全体
+    pub message: String,
+    // 詳細画面での表示用: このチケットを完了させるコミットか("fixes DIG-12" など)
+    pub closes: bool,
+}
+
+impl CommitDTO {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    // メッセージの1行目
+    pub fn summary(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+}
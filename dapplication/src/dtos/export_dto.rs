@@ -0,0 +1,31 @@
+use crate::dtos::ticket_dto::TicketDTO;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Checklist,
+    Html,
+    Csv,
+}
+
+impl From<String> for ExportFormat {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "checklist" => ExportFormat::Checklist,
+            "html" => ExportFormat::Html,
+            "csv" => ExportFormat::Csv,
+            _ => ExportFormat::Markdown,
+        }
+    }
+}
+
+// 書き出し対象。tickets は一覧と同じ順、statuses はワークフローで宣言した順
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ExportDTO {
+    pub generated_at: DateTime<Utc>,
+    pub statuses: Vec<String>,
+    pub tickets: Vec<TicketDTO>,
+}
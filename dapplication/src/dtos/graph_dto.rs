@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Mermaid,
+}
+
+impl From<String> for GraphFormat {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "mermaid" => GraphFormat::Mermaid,
+            _ => GraphFormat::Dot,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct GraphNodeDTO {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub category: String,
+}
+
+// 依存関係グラフ。辺は (ブロッカー, ブロックされるチケット) の組
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct GraphDTO {
+    pub nodes: Vec<GraphNodeDTO>,
+    pub edges: Vec<(String, String)>,
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    #[default]
+    Csv,
+    GitHub,
+    Jira,
+}
+
+impl From<String> for ImportFormat {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "github" => ImportFormat::GitHub,
+            "jira" => ImportFormat::Jira,
+            _ => ImportFormat::Csv,
+        }
+    }
+}
+
+// 取り込み元の1件。ステータスと見積もりは元の表記のまま持ち、ファイルのワークフローとスケールに合わせるのはユースケース側
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct ImportedTicketDTO {
+    // 取り込み元での ID。空の場合は重複を判定しない
+    pub external_id: String,
+    pub title: String,
+    pub status: String,
+    // todo/in-progress/done/canceled
+    pub category: String,
+    // ラベル(Three, M など)または数値。空の場合は未見積もり
+    pub estimate: String,
+    // Critical/High/Medium/Low。空の場合は Medium
+    pub priority: String,
+    // 取り込み元のユーザー名。名簿のメンバーと一致すればそのIDにする
+    pub assignee: String,
+    pub tags: Vec<String>,
+    pub due: Option<NaiveDate>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
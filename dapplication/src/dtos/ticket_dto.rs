@@ -1,26 +1,237 @@
+use crate::dtos::commit_dto::CommitDTO;
+use crate::parsers::duration_parser::format_duration;
 use chrono::{DateTime, Utc};
-use ddomain::entites::ticket::Ticket;
+use ddomain::entites::{comment::Comment, file_header::FileHeader, ticket::Ticket};
+use ddomain::value_objects::field_value::FieldValue;
+use ddomain::value_objects::recurrence::Recurrence;
+use ddomain::value_objects::source_location::SourceLocation;
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct TagDTO {
+    pub name: String,
+    pub color: String,
+    // ファイルヘッダーのタグ定義に存在するか
+    pub registered: bool,
+}
+
+impl TagDTO {
+    pub fn list(tags: &[String], header: &FileHeader) -> Vec<TagDTO> {
+        tags.iter()
+            .map(|name| TagDTO {
+                name: name.clone(),
+                color: header
+                    .tag(name)
+                    .map(|tag| tag.color.clone())
+                    .unwrap_or_default(),
+                registered: header.is_registered_tag(name),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct FieldDTO {
+    pub name: String,
+    // string/number/enum/date/bool。ヘッダーに定義が無い場合は空文字列
+    pub kind: String,
+    pub value: String,
+    pub options: Vec<String>,
+    // 定義された型に合った値か
+    pub valid: bool,
+}
+
+impl FieldDTO {
+    // ヘッダーで宣言した順に並べ、定義の無いフィールドは末尾に追加する
+    pub fn list(ticket: &Ticket, header: &FileHeader) -> Vec<FieldDTO> {
+        let declared = header.fields.iter().map(|definition| {
+            let value = ticket.fields.get(&definition.name);
+            FieldDTO {
+                name: definition.name.clone(),
+                kind: definition.kind.into(),
+                value: value.map(|value| value.to_string()).unwrap_or_default(),
+                options: definition.options.clone(),
+                valid: value.is_none_or(|value| definition.is_valid(value)),
+            }
+        });
+        let undeclared = ticket
+            .fields
+            .iter()
+            .filter(|(name, _)| header.field(name).is_none())
+            .map(|(name, value)| FieldDTO {
+                name: name.clone(),
+                value: value.to_string(),
+                ..FieldDTO::default()
+            });
+        declared.chain(undeclared).collect()
+    }
+
+    fn field_value(&self) -> Option<FieldValue> {
+        if self.value.is_empty() {
+            return None;
+        }
+        Some(match self.kind.as_str() {
+            "number" => self
+                .value
+                .parse()
+                .map_or_else(|_| FieldValue::Text(self.value.clone()), FieldValue::Number),
+            "bool" => FieldValue::Bool(self.value == "true"),
+            _ => FieldValue::Text(self.value.clone()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct CommentDTO {
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub body: String,
+}
+
+impl From<&Comment> for CommentDTO {
+    fn from(comment: &Comment) -> Self {
+        CommentDTO {
+            author: comment.author.clone(),
+            created_at: comment.created_at,
+            body: comment.body.clone(),
+        }
+    }
+}
+
+impl From<CommentDTO> for Comment {
+    fn from(dto: CommentDTO) -> Self {
+        Comment {
+            author: dto.author,
+            created_at: dto.created_at,
+            body: dto.body,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct TicketDTO {
     pub id: String,
+    // 複数のファイルをまとめて開いた場合の所属プロジェクト。1つのファイルの場合は空文字列
+    pub project: String,
+    // 取り込み元での ID。無い場合は空文字列
+    pub external_id: String,
     pub level: String,
     pub title: String,
     pub status: String,
+    // ステータスの分類(todo/in-progress/done/canceled)
+    pub status_category: String,
+    pub priority: String,
     pub created_at: DateTime<Utc>,
     pub resolved_at: Option<DateTime<Utc>>,
+    pub sprint: String,
+    pub parent: String,
+    pub blocked_by: Vec<String>,
+    pub tags: Vec<TagDTO>,
+    pub assignee: String,
+    pub due: String,
+    // 未完了のチケットの期日の区分(Overdue/Today/This week/Later)。期日が無い場合は空文字列
+    pub due_bucket: String,
+    // 繰り返しの規則(daily, weekly:mon など)。無い場合は空文字列
+    pub recurrence: String,
+    // TODO などのコメントの位置(src/main.rs:42)。無い場合は空文字列
+    pub source: String,
+    pub tracked: String,
+    pub timer_running: bool,
+    pub comments: Vec<CommentDTO>,
+    pub fields: Vec<FieldDTO>,
+    // 詳細画面の表示用: メッセージでこのチケットに触れているコミット(新しい順)
+    pub commits: Vec<CommitDTO>,
+    // 木構造表示用: 階層の深さ・子孫を含めたポイント・子の有無・折りたたみ状態
+    pub depth: usize,
+    pub points: u32,
+    pub has_children: bool,
+    pub collapsed: bool,
+    // 依存関係の表示用: 未完了のブロッカー数・ブロックしているチケット数
+    pub open_blockers: usize,
+    pub blocking: usize,
+}
+
+impl From<&Ticket> for TicketDTO {
+    fn from(ticket: &Ticket) -> Self {
+        TicketDTO {
+            id: ticket.id.clone(),
+            project: ticket.project.clone().unwrap_or_default(),
+            external_id: ticket.external_id.clone().unwrap_or_default(),
+            level: ticket.level.clone().into(),
+            title: ticket.title.clone(),
+            status: ticket.status.clone().into(),
+            status_category: ticket.status.category().into(),
+            priority: ticket.priority.into(),
+            created_at: ticket.created_at,
+            resolved_at: ticket.resolved_at,
+            sprint: ticket.sprint.clone().unwrap_or_default(),
+            parent: ticket.parent.clone().unwrap_or_default(),
+            blocked_by: ticket.blocked_by.clone(),
+            tags: TagDTO::list(&ticket.tags, &FileHeader::default()),
+            assignee: ticket.assignee.clone().unwrap_or_default(),
+            due: ticket.due.map(|due| due.to_string()).unwrap_or_default(),
+            due_bucket: ticket
+                .due_bucket(Utc::now().date_naive())
+                .map(String::from)
+                .unwrap_or_default(),
+            recurrence: ticket
+                .recurrence
+                .clone()
+                .map(String::from)
+                .unwrap_or_default(),
+            source: ticket
+                .source
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            tracked: if ticket.time_entries.is_empty() {
+                String::new()
+            } else {
+                format_duration(ticket.tracked_time(Utc::now()))
+            },
+            timer_running: ticket.is_timer_running(),
+            comments: ticket.comments.iter().map(CommentDTO::from).collect(),
+            fields: FieldDTO::list(ticket, &FileHeader::default()),
+            commits: Vec::new(),
+            depth: 0,
+            points: ticket.level.points(),
+            has_children: false,
+            collapsed: false,
+            open_blockers: 0,
+            blocking: 0,
+        }
+    }
 }
 
 impl From<TicketDTO> for Ticket {
     fn from(dto: TicketDTO) -> Self {
         Ticket {
             id: dto.id,
+            external_id: Some(dto.external_id).filter(|external_id| !external_id.is_empty()),
             level: dto.level.into(),
             title: dto.title,
             status: dto.status.into(),
+            priority: dto.priority.into(),
+            rank: 0,
             created_at: Utc::now(),
             resolved_at: None,
+            reopened_at: None,
+            sprint: Some(dto.sprint).filter(|sprint| !sprint.is_empty()),
+            parent: Some(dto.parent).filter(|parent| !parent.is_empty()),
+            blocked_by: dto.blocked_by,
+            tags: dto.tags.into_iter().map(|tag| tag.name).collect(),
+            assignee: Some(dto.assignee).filter(|assignee| !assignee.is_empty()),
+            due: dto.due.parse().ok(),
+            recurrence: Recurrence::parse(&dto.recurrence).ok(),
+            time_entries: Vec::new(),
+            comments: dto.comments.into_iter().map(Comment::from).collect(),
+            fields: dto
+                .fields
+                .iter()
+                .filter_map(|field| Some((field.name.clone(), field.field_value()?)))
+                .collect(),
+            source: SourceLocation::try_from(dto.source).ok(),
+            project: Some(dto.project).filter(|project| !project.is_empty()),
         }
     }
 }
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct TimesheetRowDTO {
+    pub key: String,
+    pub hours: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct TimesheetDTO {
+    pub from: String,
+    pub to: String,
+    pub grouping: String,
+    pub rows: Vec<TimesheetRowDTO>,
+    pub total_hours: f64,
+}
@@ -0,0 +1,30 @@
+use ddomain::entites::file_header::FileHeader;
+use ddomain::services::ticket_statistics::Workload;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct WorkloadDTO {
+    // 未割り当ての場合は空文字列
+    pub assignee: String,
+    pub name: String,
+    pub open_points: u32,
+    pub open_tickets: usize,
+}
+
+impl WorkloadDTO {
+    pub fn from_workload(workload: Workload, header: &FileHeader) -> Self {
+        let assignee = workload.assignee.unwrap_or_default();
+        let name = header
+            .members
+            .iter()
+            .find(|member| member.id == assignee)
+            .map(|member| member.name.clone())
+            .unwrap_or_default();
+        WorkloadDTO {
+            assignee,
+            name,
+            open_points: workload.open_points,
+            open_tickets: workload.open_tickets,
+        }
+    }
+}
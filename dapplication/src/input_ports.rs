@@ -1 +1,3 @@
+pub mod api_input_port;
+pub mod cli_input_port;
 pub mod terminal_input_port;
@@ -0,0 +1,26 @@
+use crate::dtos::api_dto::{TicketRequestDTO, TransitionRequestDTO, VersionedTicketDTO};
+use color_eyre::Result;
+use ddomain::value_objects::ticket_filter::TicketFilter;
+
+// HTTP API から呼び出されるユースケース。
+// 変更する操作の expected は取得時の etag で、現在の内容と異なる場合(他で変更された場合)は変更しない。"*" は確認を省く
+pub trait ApiInputPort {
+    fn list(&self, filter: TicketFilter) -> Result<Vec<VersionedTicketDTO>>;
+    fn get(&self, id: &str) -> Result<VersionedTicketDTO>;
+    // タイトルは必須。ステータスはワークフローの最初の未着手のステータスになる
+    fn create(&self, request: TicketRequestDTO) -> Result<VersionedTicketDTO>;
+    fn update(
+        &self,
+        id: &str,
+        expected: &str,
+        request: TicketRequestDTO,
+    ) -> Result<VersionedTicketDTO>;
+    fn transition(
+        &self,
+        id: &str,
+        expected: &str,
+        request: TransitionRequestDTO,
+    ) -> Result<VersionedTicketDTO>;
+    // 他のチケットからの親子関係と依存関係も取り除く
+    fn delete(&self, id: &str, expected: &str) -> Result<()>;
+}
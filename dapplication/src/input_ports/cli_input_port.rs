@@ -0,0 +1,27 @@
+use crate::dtos::graph_dto::GraphFormat;
+use crate::output_ports::code_scanner::CodeScanner;
+use crate::output_ports::ticket_exporter::TicketExporter;
+use crate::output_ports::ticket_importer::TicketImporter;
+use color_eyre::Result;
+use ddomain::value_objects::ticket_filter::TicketFilter;
+use ddomain::value_objects::timesheet_grouping::TimesheetGrouping;
+
+pub trait CliInputPort {
+    // mine の場合は現在のユーザーが担当するチケットに絞り込む
+    fn list(&self, filter: TicketFilter, mine: bool) -> Result<()>;
+    fn workload(&self) -> Result<()>;
+    // from / to は "today" や "-7d" などの日付の入力
+    fn timesheet(&self, from: &str, to: &str, grouping: TimesheetGrouping) -> Result<()>;
+    fn graph(&self, format: GraphFormat) -> Result<()>;
+    // 投稿者は現在のユーザー
+    fn comment(&self, id: &str, body: &str) -> Result<()>;
+    // 期日が来た繰り返しのチケットを作る
+    fn tick(&self) -> Result<()>;
+    // list と同じ条件で絞り込んだチケットを exporter の形式で出力する
+    fn export(&self, filter: TicketFilter, mine: bool, exporter: &dyn TicketExporter)
+        -> Result<()>;
+    // 取り込み元の ID が既にあるチケットは飛ばす。dry_run の場合は保存せずに結果だけを出力する
+    fn import(&self, importer: &dyn TicketImporter, dry_run: bool) -> Result<()>;
+    // TODO などのコメントとチケットを同期する。コメントが消えたチケットは解決する
+    fn scan(&self, scanner: &dyn CodeScanner, dry_run: bool) -> Result<()>;
+}
@@ -7,4 +7,38 @@ pub trait TerminalInputPort {
     fn next_row(&mut self) -> Result<()>;
     fn previous_row(&mut self) -> Result<()>;
     fn enter_edit_mode(&mut self) -> Result<()>;
+    fn enter_chart_mode(&mut self) -> Result<()>;
+    fn enter_normal_mode(&mut self) -> Result<()>;
+    fn cycle_chart_window(&mut self) -> Result<()>;
+    fn toggle_sprint_filter(&mut self) -> Result<()>;
+    fn toggle_mine_filter(&mut self) -> Result<()>;
+    fn cycle_assignee(&mut self) -> Result<()>;
+    fn cycle_estimate(&mut self) -> Result<()>;
+    fn cycle_priority(&mut self) -> Result<()>;
+    // up の場合は同じ優先度の中で1つ上に移動する
+    fn move_rank(&mut self, up: bool) -> Result<()>;
+    fn edit_tags(&mut self) -> Result<()>;
+    fn edit_tag_filter(&mut self) -> Result<()>;
+    fn edit_due_date(&mut self) -> Result<()>;
+    fn enter_agenda_mode(&mut self) -> Result<()>;
+    fn enter_board_mode(&mut self) -> Result<()>;
+    fn is_input_mode(&self) -> bool;
+    fn input_char(&mut self, c: char) -> Result<()>;
+    fn input_backspace(&mut self) -> Result<()>;
+    fn submit_input(&mut self) -> Result<()>;
+    fn cancel_input(&mut self) -> Result<()>;
+    fn cycle_status(&mut self, force: bool) -> Result<()>;
+    fn toggle_collapse(&mut self) -> Result<()>;
+    fn add_comment(&mut self) -> Result<()>;
+    fn next_field(&mut self) -> Result<()>;
+    fn edit_field(&mut self) -> Result<()>;
+    fn toggle_timer(&mut self) -> Result<()>;
+    fn edit_recurrence(&mut self) -> Result<()>;
+    fn log_time(&mut self) -> Result<()>;
+    fn cycle_ticket_sprint(&mut self) -> Result<()>;
+    fn close_current_sprint(&mut self) -> Result<()>;
+    fn cycle_project(&mut self) -> Result<()>;
+    // コミットで修正済みとされた未完了のチケットを完了にする
+    fn resolve_fixed(&mut self) -> Result<()>;
+    fn create_branch(&mut self) -> Result<()>;
 }
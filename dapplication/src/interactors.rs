@@ -1 +1,3 @@
+pub mod api_interactor;
+pub mod cli_interactor;
 pub mod terminal_interactor;
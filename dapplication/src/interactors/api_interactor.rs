@@ -0,0 +1,301 @@
+use crate::dtos::api_dto::{TicketRequestDTO, TransitionRequestDTO, VersionedTicketDTO};
+use crate::dtos::ticket_dto::{FieldDTO, TagDTO, TicketDTO};
+use crate::input_ports::api_input_port::ApiInputPort;
+use crate::parsers::date_parser::parse_date;
+use chrono::Utc;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use ddomain::domain_errors::DomainError;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint, ticket::Ticket};
+use ddomain::repositories::ticket_repository::TicketRepository;
+use ddomain::services::{
+    status_transition, ticket_dependencies, ticket_hierarchy, ticket_ids, ticket_ordering,
+};
+use ddomain::value_objects::field_type::FieldType;
+use ddomain::value_objects::priority::Priority;
+use ddomain::value_objects::recurrence::Recurrence;
+use ddomain::value_objects::ticket_filter::TicketFilter;
+use ddomain::value_objects::ticket_level::TicketLevel;
+use ddomain::value_objects::ticket_status::TicketStatus;
+
+// チケットの内容から求める版。内容が同じなら同じ値になる(FNV-1a)
+fn etag(ticket: &Ticket) -> String {
+    let hash = format!("{:?}", ticket)
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
+
+fn versioned(ticket: &Ticket, header: &FileHeader) -> VersionedTicketDTO {
+    VersionedTicketDTO {
+        ticket: TicketDTO {
+            tags: TagDTO::list(&ticket.tags, header),
+            fields: FieldDTO::list(ticket, header),
+            ..TicketDTO::from(ticket)
+        },
+        etag: etag(ticket),
+    }
+}
+
+fn find<'a>(tickets: &'a [Ticket], id: &str) -> Result<&'a Ticket, DomainError> {
+    tickets
+        .iter()
+        .find(|ticket| ticket.id == id)
+        .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))
+}
+
+fn find_mut<'a>(tickets: &'a mut [Ticket], id: &str) -> Result<&'a mut Ticket, DomainError> {
+    tickets
+        .iter_mut()
+        .find(|ticket| ticket.id == id)
+        .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))
+}
+
+// 取得した後に他で変更されていないことを確かめる
+fn check_version(tickets: &[Ticket], id: &str, expected: &str) -> Result<(), DomainError> {
+    let ticket = find(tickets, id)?;
+    if expected != "*" && expected != etag(ticket) {
+        return Err(DomainError::StaleTicket(id.to_string()));
+    }
+    Ok(())
+}
+
+// 空文字列は値を取り除く
+fn non_empty(value: String) -> Option<String> {
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+// リクエストで指定された項目だけをチケットに反映する。途中で失敗した場合は呼び出し側で保存しない
+fn apply(
+    tickets: &mut [Ticket],
+    id: &str,
+    request: TicketRequestDTO,
+    header: &FileHeader,
+    sprints: &[Sprint],
+) -> Result<()> {
+    if let Some(parent) = request.parent {
+        ticket_hierarchy::set_parent(tickets, id, non_empty(parent).as_deref())?;
+    }
+    if let Some(blocked_by) = request.blocked_by {
+        find_mut(tickets, id)?.blocked_by.clear();
+        for blocker in blocked_by {
+            ticket_dependencies::add_dependency(tickets, id, &blocker)?;
+        }
+    }
+
+    let today = Utc::now().date_naive();
+    let ticket = find_mut(tickets, id)?;
+    if let Some(title) = request.title {
+        ticket.title = non_empty(title).ok_or_else(|| eyre!("The title must not be empty."))?;
+    }
+    if let Some(level) = request.level {
+        // スケールのラベルでも数値でもない値で見積もりを消さないようにする
+        let known = header.estimate_points(level.trim()).is_some()
+            || level.trim().parse::<f64>().is_ok_and(|value| value > 0.0);
+        if non_empty(level.clone()).is_some() && !known {
+            let labels: Vec<String> = header
+                .estimation_levels()
+                .iter()
+                .map(|level| level.label().to_string())
+                .collect();
+            return Err(eyre!(
+                "'{}' is not an estimate. Use {} or a number.",
+                level,
+                labels.join(", ")
+            ));
+        }
+        ticket.level = header.level_for(&level);
+    }
+    if let Some(priority) = request.priority {
+        ticket.priority = Priority::parse(&priority).ok_or_else(|| {
+            let names: Vec<String> = Priority::ALL.into_iter().map(String::from).collect();
+            eyre!(
+                "'{}' is not a priority. Use {}.",
+                priority,
+                names.join(", ")
+            )
+        })?;
+    }
+    if let Some(assignee) = request.assignee {
+        // 名簿のメンバーと一致する場合はメンバーのIDにする
+        ticket.assignee = non_empty(assignee).map(|assignee| {
+            header
+                .find_member(std::slice::from_ref(&assignee))
+                .map_or(assignee.clone(), |member| member.id.clone())
+        });
+    }
+    if let Some(sprint) = request.sprint {
+        let sprint = non_empty(sprint);
+        if let Some(name) = &sprint {
+            if !sprints.iter().any(|known| known.name == *name) {
+                return Err(DomainError::SprintNotFound(name.clone()).into());
+            }
+        }
+        ticket.sprint = sprint;
+    }
+    if let Some(tags) = request.tags {
+        ticket.set_tags(tags);
+    }
+    if let Some(due) = request.due {
+        ticket.due = parse_date(&due, today).map_err(|message| eyre!(message))?;
+    }
+    if let Some(recurrence) = request.recurrence {
+        ticket.recurrence = match non_empty(recurrence) {
+            Some(recurrence) => Some(Recurrence::parse(&recurrence)?),
+            None => None,
+        };
+    }
+    if let Some(external_id) = request.external_id {
+        ticket.external_id = non_empty(external_id);
+    }
+    for (name, value) in request.fields.unwrap_or_default() {
+        let definition = header
+            .field(&name)
+            .ok_or_else(|| DomainError::UnknownField(name.clone()))?;
+        // 日付は "tomorrow" や "+3d" などの入力も受け付ける
+        let value = match definition.kind {
+            FieldType::Date => parse_date(&value, today)
+                .map_err(|message| eyre!(message))?
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            _ => value,
+        };
+        ticket.set_field(definition, &value)?;
+    }
+    Ok(())
+}
+
+pub struct ApiInteractor<R: TicketRepository> {
+    repository: R,
+    current_user: Vec<String>,
+}
+
+impl<R: TicketRepository> ApiInteractor<R> {
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository,
+            current_user: Vec::new(),
+        }
+    }
+
+    // 作業中への移行で開始するタイマーの記録者。git の user.name / user.email や $USER など
+    pub fn with_current_user(mut self, identities: Vec<String>) -> Self {
+        self.current_user = identities;
+        self
+    }
+
+    // 変更を保存し、変更後のチケットを返す
+    fn save(&self, tickets: &[Ticket], id: &str) -> Result<VersionedTicketDTO> {
+        self.repository.save_tickets(tickets)?;
+        self.get(id)
+    }
+}
+
+impl<R: TicketRepository> ApiInputPort for ApiInteractor<R> {
+    fn list(&self, filter: TicketFilter) -> Result<Vec<VersionedTicketDTO>> {
+        let tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+        let mut matched: Vec<&Ticket> = tickets
+            .iter()
+            .filter(|ticket| filter.matches(ticket))
+            .collect();
+        matched.sort_by(|a, b| ticket_ordering::compare(a, b));
+        Ok(matched
+            .into_iter()
+            .map(|ticket| versioned(ticket, &header))
+            .collect())
+    }
+
+    fn get(&self, id: &str) -> Result<VersionedTicketDTO> {
+        let tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+        Ok(versioned(find(&tickets, id)?, &header))
+    }
+
+    fn create(&self, request: TicketRequestDTO) -> Result<VersionedTicketDTO> {
+        let mut tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+        let sprints = self.repository.fetch_sprints()?;
+        if request
+            .title
+            .as_deref()
+            .is_none_or(|title| title.trim().is_empty())
+        {
+            return Err(eyre!("A title is required to create a ticket."));
+        }
+
+        let prefix = ticket_ids::file_prefix(&tickets).to_string();
+        let id = ticket_ids::next_id(&tickets, &prefix);
+        tickets.push(Ticket::new(
+            id.clone(),
+            TicketLevel::Unestimated,
+            String::new(),
+            header.workflow().initial_status(),
+        ));
+        apply(&mut tickets, &id, request, &header, &sprints)?;
+        self.save(&tickets, &id)
+    }
+
+    fn update(
+        &self,
+        id: &str,
+        expected: &str,
+        request: TicketRequestDTO,
+    ) -> Result<VersionedTicketDTO> {
+        let mut tickets = self.repository.fetch_tickets()?;
+        check_version(&tickets, id, expected)?;
+        let header = self.repository.fetch_header()?;
+        let sprints = self.repository.fetch_sprints()?;
+        apply(&mut tickets, id, request, &header, &sprints)?;
+        self.save(&tickets, id)
+    }
+
+    fn transition(
+        &self,
+        id: &str,
+        expected: &str,
+        request: TransitionRequestDTO,
+    ) -> Result<VersionedTicketDTO> {
+        let mut tickets = self.repository.fetch_tickets()?;
+        check_version(&tickets, id, expected)?;
+        let header = self.repository.fetch_header()?;
+        let workflow = header.workflow();
+        // 宣言されたステータスの名前は大文字小文字を区別しない
+        let status = workflow
+            .statuses
+            .iter()
+            .find(|status| status.name.eq_ignore_ascii_case(&request.status))
+            .map_or_else(
+                || TicketStatus::from(request.status),
+                |status| status.status(),
+            );
+        let actor = header
+            .find_member(&self.current_user)
+            .map(|member| member.id.clone());
+        status_transition::change_status(
+            &mut tickets,
+            &workflow,
+            id,
+            status,
+            request.force,
+            actor.as_deref(),
+        )?;
+        self.save(&tickets, id)
+    }
+
+    fn delete(&self, id: &str, expected: &str) -> Result<()> {
+        let mut tickets = self.repository.fetch_tickets()?;
+        check_version(&tickets, id, expected)?;
+        tickets.retain(|ticket| ticket.id != id);
+        for ticket in &mut tickets {
+            ticket.blocked_by.retain(|blocker| blocker != id);
+            if ticket.parent.as_deref() == Some(id) {
+                ticket.parent = None;
+            }
+        }
+        self.repository.save_tickets(&tickets)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,497 @@
+use crate::dtos::export_dto::ExportDTO;
+use crate::dtos::graph_dto::{GraphDTO, GraphFormat, GraphNodeDTO};
+use crate::dtos::ticket_dto::{FieldDTO, TagDTO, TicketDTO};
+use crate::dtos::timesheet_dto::{TimesheetDTO, TimesheetRowDTO};
+use crate::dtos::workload_dto::WorkloadDTO;
+use crate::input_ports::cli_input_port::CliInputPort;
+use crate::output_ports::cli_output_port::CliOutputPort;
+use crate::output_ports::code_scanner::CodeScanner;
+use crate::output_ports::ticket_exporter::TicketExporter;
+use crate::output_ports::ticket_importer::TicketImporter;
+use crate::parsers::date_parser::parse_date;
+use chrono::Utc;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use ddomain::domain_errors::DomainError;
+use ddomain::entites::{file_header::FileHeader, ticket::Ticket};
+use ddomain::repositories::ticket_repository::TicketRepository;
+use ddomain::services::{
+    status_transition, ticket_dependencies, ticket_ids, ticket_ordering, ticket_recurrence,
+    ticket_statistics, time_tracking,
+};
+use ddomain::value_objects::source_location::SourceLocation;
+use ddomain::value_objects::status_category::StatusCategory;
+use ddomain::value_objects::ticket_filter::TicketFilter;
+use ddomain::value_objects::ticket_level::TicketLevel;
+use ddomain::value_objects::timesheet_grouping::TimesheetGrouping;
+
+// サブコマンドから呼び出されるユースケース
+pub struct CliInteractor<R: TicketRepository, O: CliOutputPort> {
+    repository: R,
+    output_port: O,
+    current_user: Vec<String>,
+}
+
+impl<R: TicketRepository, O: CliOutputPort> CliInteractor<R, O> {
+    pub fn new(repository: R, output_port: O) -> Self {
+        Self {
+            repository,
+            output_port,
+            current_user: Vec::new(),
+        }
+    }
+
+    // --mine やコメントの投稿者で使う現在のユーザーの候補(git の user.email など)
+    pub fn with_current_user(mut self, identities: Vec<String>) -> Self {
+        self.current_user = identities;
+        self
+    }
+
+    // 絞り込みと並べ替えを済ませたチケット。ヘッダーとの不整合は警告として出力する
+    fn select_tickets(
+        &self,
+        mut filter: TicketFilter,
+        mine: bool,
+    ) -> Result<(FileHeader, Vec<TicketDTO>)> {
+        let tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+
+        if mine {
+            let member = header
+                .find_member(&self.current_user)
+                .ok_or_else(|| DomainError::UnknownMember(self.current_user.join(", ")))?;
+            filter.assignee = Some(member.id.clone());
+        }
+
+        let warnings: Vec<String> = header
+            .unregistered_tags(&tickets)
+            .into_iter()
+            .map(|(id, tag)| format!("{}: tag '{}' is not defined in the header", id, tag))
+            .chain(
+                header
+                    .unregistered_assignees(&tickets)
+                    .into_iter()
+                    .map(|(id, assignee)| {
+                        format!("{}: assignee '{}' is not in the member list", id, assignee)
+                    }),
+            )
+            .chain(
+                header
+                    .workflow()
+                    .undeclared_statuses(&tickets)
+                    .into_iter()
+                    .map(|(id, status)| {
+                        format!("{}: status '{}' is not declared in the header", id, status)
+                    }),
+            )
+            .chain(
+                header
+                    .invalid_levels(&tickets)
+                    .into_iter()
+                    .map(|(id, level)| {
+                        format!("{}: estimate '{}' is not on the file's scale", id, level)
+                    }),
+            )
+            .chain(
+                header
+                    .invalid_fields(&tickets)
+                    .into_iter()
+                    .map(|(id, field)| {
+                        format!(
+                            "{}: field '{}' is undefined or has an invalid value",
+                            id, field
+                        )
+                    }),
+            )
+            .collect();
+        self.output_port.present_warnings(&warnings);
+
+        let mut matched: Vec<&Ticket> = tickets
+            .iter()
+            .filter(|ticket| filter.matches(ticket))
+            .collect();
+        matched.sort_by(|a, b| ticket_ordering::compare(a, b));
+        let ticket_dtos: Vec<TicketDTO> = matched
+            .into_iter()
+            .map(|ticket| TicketDTO {
+                tags: TagDTO::list(&ticket.tags, &header),
+                fields: FieldDTO::list(ticket, &header),
+                ..TicketDTO::from(ticket)
+            })
+            .collect();
+        Ok((header, ticket_dtos))
+    }
+}
+
+impl<R: TicketRepository, O: CliOutputPort> CliInputPort for CliInteractor<R, O> {
+    fn list(&self, filter: TicketFilter, mine: bool) -> Result<()> {
+        let (_, ticket_dtos) = self.select_tickets(filter, mine)?;
+        self.output_port.present_tickets(&ticket_dtos);
+        Ok(())
+    }
+
+    fn workload(&self) -> Result<()> {
+        let tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+
+        let workload: Vec<WorkloadDTO> =
+            ticket_statistics::workload_by_assignee(&tickets, &header.members)
+                .into_iter()
+                .map(|workload| WorkloadDTO::from_workload(workload, &header))
+                .collect();
+        self.output_port.present_workload(&workload);
+        Ok(())
+    }
+
+    fn timesheet(&self, from: &str, to: &str, grouping: TimesheetGrouping) -> Result<()> {
+        let now = Utc::now();
+        let today = now.date_naive();
+        let from = parse_date(from, today)
+            .map_err(|message| eyre!(message))?
+            .unwrap_or(today);
+        let to = parse_date(to, today)
+            .map_err(|message| eyre!(message))?
+            .unwrap_or(today);
+
+        let tickets = self.repository.fetch_tickets()?;
+        let rows: Vec<TimesheetRowDTO> =
+            time_tracking::timesheet(&tickets, from, to, grouping, now)
+                .into_iter()
+                .map(|row| TimesheetRowDTO {
+                    key: row.key,
+                    hours: row.duration.num_minutes() as f64 / 60.0,
+                })
+                .collect();
+        let timesheet = TimesheetDTO {
+            from: from.to_string(),
+            to: to.to_string(),
+            grouping: grouping.into(),
+            total_hours: rows.iter().fold(0.0, |total, row| total + row.hours),
+            rows,
+        };
+        self.output_port.present_timesheet(&timesheet);
+        Ok(())
+    }
+
+    fn graph(&self, format: GraphFormat) -> Result<()> {
+        let tickets = self.repository.fetch_tickets()?;
+        ticket_dependencies::validate_dependencies(&tickets)?;
+
+        let graph = GraphDTO {
+            nodes: tickets
+                .iter()
+                .map(|ticket| GraphNodeDTO {
+                    id: ticket.id.clone(),
+                    title: ticket.title.clone(),
+                    status: ticket.status.clone().into(),
+                    category: ticket.status.category().into(),
+                })
+                .collect(),
+            edges: tickets
+                .iter()
+                .flat_map(|ticket| {
+                    ticket
+                        .blocked_by
+                        .iter()
+                        .map(|blocker| (blocker.clone(), ticket.id.clone()))
+                })
+                .collect(),
+        };
+        self.output_port.present_graph(&graph, format);
+        Ok(())
+    }
+
+    fn comment(&self, id: &str, body: &str) -> Result<()> {
+        let mut tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+        let ticket = tickets
+            .iter_mut()
+            .find(|ticket| ticket.id == id)
+            .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))?;
+        let author = header.author_name(&self.current_user);
+        ticket.add_comment(author.clone(), body, Utc::now())?;
+        self.repository.save_tickets(&tickets)?;
+        self.output_port
+            .present_message(&format!("Added a comment by {} to {}", author, id));
+        Ok(())
+    }
+
+    fn tick(&self) -> Result<()> {
+        let mut tickets = self.repository.fetch_tickets()?;
+        let workflow = self.repository.fetch_header()?.workflow();
+        let spawned =
+            ticket_recurrence::materialize_due(&mut tickets, &workflow, Utc::now().date_naive());
+        if spawned.is_empty() {
+            self.output_port
+                .present_message("No recurring tickets are due.");
+            return Ok(());
+        }
+        self.repository.save_tickets(&tickets)?;
+        for id in &spawned {
+            let due = tickets
+                .iter()
+                .find(|ticket| &ticket.id == id)
+                .and_then(|ticket| ticket.due)
+                .map(|due| due.to_string())
+                .unwrap_or_default();
+            self.output_port
+                .present_message(&format!("Created {} due {}", id, due));
+        }
+        Ok(())
+    }
+
+    fn export(
+        &self,
+        filter: TicketFilter,
+        mine: bool,
+        exporter: &dyn TicketExporter,
+    ) -> Result<()> {
+        let (header, tickets) = self.select_tickets(filter, mine)?;
+        let export = ExportDTO {
+            generated_at: Utc::now(),
+            statuses: header
+                .workflow()
+                .statuses
+                .iter()
+                .map(|status| status.name.clone())
+                .collect(),
+            tickets,
+        };
+        self.output_port.present_export(&exporter.export(&export)?);
+        Ok(())
+    }
+
+    fn import(&self, importer: &dyn TicketImporter, dry_run: bool) -> Result<()> {
+        let imported = importer.read()?;
+        let mut tickets = self.repository.fetch_tickets()?;
+        let header = self.repository.fetch_header()?;
+        let workflow = header.workflow();
+        let prefix = ticket_ids::file_prefix(&tickets).to_string();
+        let now = Utc::now();
+
+        let mut added = 0;
+        let mut skipped = 0;
+        for item in imported {
+            if item.title.trim().is_empty() {
+                self.output_port.present_warnings(&[format!(
+                    "{}: skipped an entry without a title",
+                    item.external_id
+                )]);
+                skipped += 1;
+                continue;
+            }
+            if let Some(existing) = tickets.iter().find(|ticket| {
+                !item.external_id.is_empty()
+                    && ticket.external_id.as_deref() == Some(item.external_id.as_str())
+            }) {
+                self.output_port.present_message(&format!(
+                    "Skipped {}: already imported as {}",
+                    item.external_id, existing.id
+                ));
+                skipped += 1;
+                continue;
+            }
+
+            let mut ticket = Ticket::new(
+                ticket_ids::next_id(&tickets, &prefix),
+                header.level_for(&item.estimate),
+                item.title.trim().to_string(),
+                workflow.status_for(&item.status, item.category.into()),
+            );
+            ticket.external_id = Some(item.external_id).filter(|id| !id.is_empty());
+            ticket.priority = item.priority.into();
+            // 名簿のメンバーと一致するユーザー名はメンバーのIDに置き換える
+            ticket.assignee = Some(item.assignee)
+                .filter(|assignee| !assignee.is_empty())
+                .map(|assignee| {
+                    header
+                        .find_member(std::slice::from_ref(&assignee))
+                        .map_or(assignee.clone(), |member| member.id.clone())
+                });
+            ticket.set_tags(item.tags);
+            ticket.due = item.due;
+            ticket.created_at = item.created_at.unwrap_or(now);
+            if ticket.status.category() == StatusCategory::Done {
+                ticket.resolved_at = item.resolved_at.or(Some(now));
+            }
+
+            self.output_port.present_message(&format!(
+                "{} {}{}: {} [{}, {}]",
+                if dry_run { "Would import" } else { "Imported" },
+                ticket.id,
+                ticket
+                    .external_id
+                    .as_ref()
+                    .map(|id| format!(" from {}", id))
+                    .unwrap_or_default(),
+                ticket.title,
+                ticket.status.name(),
+                ticket.level.label()
+            ));
+            tickets.push(ticket);
+            added += 1;
+        }
+
+        if dry_run {
+            self.output_port.present_message(&format!(
+                "Dry run: {} to import, {} skipped. Nothing was saved.",
+                added, skipped
+            ));
+            return Ok(());
+        }
+        if added > 0 {
+            self.repository.save_tickets(&tickets)?;
+        }
+        self.output_port
+            .present_message(&format!("{} imported, {} skipped.", added, skipped));
+        Ok(())
+    }
+
+    fn scan(&self, scanner: &dyn CodeScanner, dry_run: bool) -> Result<()> {
+        let found = scanner.scan()?;
+        let mut tickets = self.repository.fetch_tickets()?;
+        let workflow = self.repository.fetch_header()?.workflow();
+        let prefix = ticket_ids::file_prefix(&tickets).to_string();
+        let verb = |done: &'static str, planned: &'static str| if dry_run { planned } else { done };
+
+        // 行は前後の編集でずれるため、コメントとチケットはファイルとタイトルで対応付ける
+        let mut matched: Vec<usize> = Vec::new();
+        let (mut added, mut updated, mut resolved) = (0, 0, 0);
+        for item in found {
+            let title = item.marker.title();
+            let candidates = || {
+                tickets.iter().enumerate().filter(|(index, ticket)| {
+                    !matched.contains(index)
+                        && ticket.title == title
+                        && ticket
+                            .source
+                            .as_ref()
+                            .is_some_and(|source| source.path == item.location.path)
+                })
+            };
+            let existing = candidates()
+                .find(|(_, ticket)| ticket.is_open())
+                .or_else(|| candidates().next())
+                .map(|(index, _)| index);
+
+            let Some(index) = existing else {
+                let mut ticket = Ticket::new(
+                    ticket_ids::next_id(&tickets, &prefix),
+                    TicketLevel::Unestimated,
+                    title,
+                    workflow.initial_status(),
+                );
+                ticket.set_tags(vec![item.marker.tag()]);
+                self.output_port.present_message(&format!(
+                    "{} {}: {} ({})",
+                    verb("Added", "Would add"),
+                    ticket.id,
+                    ticket.title,
+                    item.location
+                ));
+                ticket.source = Some(item.location);
+                matched.push(tickets.len());
+                tickets.push(ticket);
+                added += 1;
+                continue;
+            };
+
+            matched.push(index);
+            let id = tickets[index].id.clone();
+            // 完了にしたチケットのコメントが再び現れた場合は作業に戻す。中止したチケットはそのままにする
+            if tickets[index].status.category() == StatusCategory::Done {
+                let reopened = status_transition::change_status(
+                    &mut tickets,
+                    &workflow,
+                    &id,
+                    workflow.initial_status(),
+                    false,
+                    None,
+                );
+                match reopened {
+                    Ok(_) => {
+                        tickets[index].resolved_at = None;
+                        self.output_port.present_message(&format!(
+                            "{} {}: the comment is back at {}",
+                            verb("Reopened", "Would reopen"),
+                            id,
+                            item.location
+                        ));
+                        updated += 1;
+                    }
+                    Err(error) => self.output_port.present_warnings(&[format!(
+                        "{}: the comment is back at {}, but {}",
+                        id, item.location, error
+                    )]),
+                }
+            } else if tickets[index].source.as_ref() != Some(&item.location) {
+                updated += 1;
+            }
+            tickets[index].source = Some(item.location);
+        }
+
+        // コメントが消えたチケットは、子チケットやタイマー、繰り返しの規則に従って完了にする
+        let removed: Vec<(String, SourceLocation)> = tickets
+            .iter()
+            .enumerate()
+            .filter(|(index, ticket)| !matched.contains(index) && ticket.is_open())
+            .filter_map(|(_, ticket)| Some((ticket.id.clone(), ticket.source.clone()?)))
+            .filter(|(_, source)| scanner.covers(&source.path))
+            .collect();
+        for (id, source) in removed {
+            let Some(ticket) = tickets.iter().find(|ticket| ticket.id == id) else {
+                continue;
+            };
+            let (title, status) = (ticket.title.clone(), ticket.status.clone());
+            let Some(done) = workflow.resolved_status(&status) else {
+                self.output_port.present_warnings(&[format!(
+                    "{}: the comment at {} was removed, but no done status can follow {}",
+                    id,
+                    source,
+                    status.name()
+                )]);
+                continue;
+            };
+            match status_transition::change_status(&mut tickets, &workflow, &id, done, false, None)
+            {
+                Ok(spawned) => {
+                    self.output_port.present_message(&format!(
+                        "{} {}: {} (removed from {})",
+                        verb("Resolved", "Would resolve"),
+                        id,
+                        title,
+                        source
+                    ));
+                    if let Some(spawned) = spawned {
+                        self.output_port.present_message(&format!(
+                            "{} the next occurrence {}",
+                            verb("Created", "Would create"),
+                            spawned
+                        ));
+                    }
+                    resolved += 1;
+                }
+                Err(error) => self.output_port.present_warnings(&[format!(
+                    "{}: the comment at {} was removed, but {}",
+                    id, source, error
+                )]),
+            }
+        }
+
+        if dry_run {
+            self.output_port.present_message(&format!(
+                "Dry run: {} to add, {} to update, {} to resolve. Nothing was saved.",
+                added, updated, resolved
+            ));
+            return Ok(());
+        }
+        if added + updated + resolved > 0 {
+            self.repository.save_tickets(&tickets)?;
+        }
+        self.output_port.present_message(&format!(
+            "{} added, {} updated, {} resolved.",
+            added, updated, resolved
+        ));
+        Ok(())
+    }
+}
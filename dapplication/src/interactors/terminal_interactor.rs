@@ -1,8 +1,30 @@
-use crate::dtos::ticket_dto::TicketDTO;
+use crate::dtos::agenda_dto::AgendaGroupDTO;
+use crate::dtos::board_dto::BoardColumnDTO;
+use crate::dtos::chart_dto::ChartDTO;
+use crate::dtos::commit_dto::CommitDTO;
+use crate::dtos::ticket_dto::{FieldDTO, TagDTO, TicketDTO};
 use crate::input_ports::terminal_input_port::TerminalInputPort;
 use crate::output_ports::terminal_output_port::TerminalOutputPort;
+use crate::output_ports::version_control::VersionControl;
+use crate::parsers::date_parser::parse_date;
+use crate::parsers::duration_parser::parse_duration;
+use crate::parsers::query_parser::parse_condition;
+use chrono::{DateTime, NaiveDate, Utc};
 use color_eyre::Result;
+use ddomain::domain_errors::DomainError;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint};
 use ddomain::repositories::ticket_repository::TicketRepository;
+use ddomain::services::commit_references::{self, TicketReference};
+use ddomain::services::{
+    sprint_planning, status_transition, ticket_dependencies, ticket_hierarchy, ticket_ordering,
+    ticket_statistics,
+};
+use ddomain::value_objects::chart_window::ChartWindow;
+use ddomain::value_objects::due_bucket::DueBucket;
+use ddomain::value_objects::field_type::FieldType;
+use ddomain::value_objects::field_value::FieldValue;
+use ddomain::value_objects::recurrence::Recurrence;
+use ddomain::value_objects::ticket_filter::TicketFilter;
 use ddomain::{entites::ticket::Ticket, value_objects::app_mode::AppMode};
 use ratatui::{
     crossterm::event::{self, Event, KeyCode},
@@ -10,12 +32,78 @@ use ratatui::{
     widgets::TableState,
     Frame,
 };
+use std::collections::HashSet;
+
+// 入力欄で編集している対象
+enum InputTarget {
+    Tags,
+    TagFilter,
+    DueDate,
+    TimeLog,
+    Comment,
+    // 値を入力するカスタムフィールドの名前
+    Field(String),
+    Recurrence,
+}
+
+impl InputTarget {
+    fn prompt(&self) -> String {
+        match self {
+            InputTarget::Tags => "Tags (comma separated)".to_string(),
+            InputTarget::TagFilter => "Filter by tags or field=value (empty to clear)".to_string(),
+            InputTarget::DueDate => "Due date (tomorrow, fri, +3d, 2024-05-01, none)".to_string(),
+            InputTarget::TimeLog => {
+                "Time spent and note (e.g. 1h30m reviewed the patch)".to_string()
+            }
+            InputTarget::Comment => "Comment".to_string(),
+            InputTarget::Field(name) => format!("{} (empty to clear)", name),
+            InputTarget::Recurrence => {
+                "Repeat (daily, weekly:mon, monthly:15, cron:0 9 * * 1-5, none)".to_string()
+            }
+        }
+    }
+
+    // 入力を終えた後に戻る画面(コメントは詳細画面から入力する)
+    fn return_mode(&self) -> AppMode {
+        match self {
+            InputTarget::Comment | InputTarget::Field(_) => AppMode::Edit,
+            _ => AppMode::Normal,
+        }
+    }
+}
+
+// カンマまたは空白区切りのタグ入力を分割する
+fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
 
 pub struct TerminalInteractor<R: TicketRepository, O: TerminalOutputPort> {
     state: TableState,
     mode: AppMode,
+    chart_window: ChartWindow,
     selected_ticket_index: Option<usize>,
+    // 詳細画面で選択中のカスタムフィールド
+    selected_field: usize,
     items: Vec<Ticket>,
+    sprints: Vec<Sprint>,
+    header: FileHeader,
+    // 複数のファイルを開いた場合のプロジェクト(ファイル)の一覧
+    projects: Vec<String>,
+    current_sprint_only: bool,
+    filter: TicketFilter,
+    input: String,
+    input_target: Option<InputTarget>,
+    current_user: Vec<String>,
+    collapsed: HashSet<String>,
+    notice: Option<String>,
+    // チケットファイルを管理している git リポジトリ。管理下に無い場合は None
+    version_control: Option<Box<dyn VersionControl>>,
+    // 履歴のうちチケットに触れているコミットと、その言及
+    commits: Vec<(CommitDTO, Vec<TicketReference>)>,
     repository: R,
     output_port: O,
 }
@@ -23,68 +111,1054 @@ pub struct TerminalInteractor<R: TicketRepository, O: TerminalOutputPort> {
 impl<R: TicketRepository, O: TerminalOutputPort> TerminalInteractor<R, O> {
     pub fn new(repository: R, output_port: O) -> Result<Self> {
         let items = repository.fetch_tickets()?;
+        let sprints = repository.fetch_sprints()?;
+        let header = repository.fetch_header()?;
+        // 存在しない親やブロッカー、循環した参照があっても開けるようにし、起動時に通知する
+        let mut notices = Vec::new();
+        if let Err(error) = ticket_hierarchy::validate_hierarchy(&items) {
+            notices.push(error.to_string());
+        }
+        if let Err(error) = ticket_dependencies::validate_dependencies(&items) {
+            notices.push(error.to_string());
+        }
+
+        // 定義されていないタグや名簿に無い担当者は起動時に通知する
+        let unregistered: Vec<String> = header
+            .unregistered_tags(&items)
+            .into_iter()
+            .chain(header.unregistered_assignees(&items))
+            .chain(header.invalid_fields(&items))
+            .chain(header.workflow().undeclared_statuses(&items))
+            .chain(header.invalid_levels(&items))
+            .map(|(id, name)| format!("{}:{}", id, name))
+            .collect();
+        if !unregistered.is_empty() {
+            notices.push(format!(
+                "Unregistered tags/assignees/fields/statuses/estimates: {}",
+                unregistered.join(", ")
+            ));
+        }
+        let notice = (!notices.is_empty()).then(|| notices.join(" "));
+
         Ok(Self {
             state: TableState::default().with_selected(0),
             mode: AppMode::Normal,
+            chart_window: ChartWindow::default(),
             selected_ticket_index: None,
+            selected_field: 0,
             items,
+            sprints,
+            header,
+            projects: repository.projects(),
+            // 既定では進行中のスプリントのチケットのみ表示する
+            current_sprint_only: true,
+            filter: TicketFilter::default(),
+            input: String::new(),
+            input_target: None,
+            current_user: Vec::new(),
+            collapsed: HashSet::new(),
+            notice,
+            version_control: None,
+            commits: Vec::new(),
             repository,
             output_port,
         })
     }
 
+    // 「自分の担当」フィルタで使う現在のユーザーの候補(git の user.email など)
+    pub fn with_current_user(mut self, identities: Vec<String>) -> Self {
+        self.current_user = identities;
+        self
+    }
+
+    // 履歴からチケットに触れているコミットを読み込み、完了を示すコミットがある未完了のチケットを通知する
+    pub fn with_version_control(mut self, version_control: Box<dyn VersionControl>) -> Self {
+        match version_control.commits() {
+            Ok(commits) => {
+                self.commits = commits
+                    .into_iter()
+                    .map(|commit| {
+                        let references =
+                            commit_references::references(&commit.message, &self.items);
+                        (commit, references)
+                    })
+                    .filter(|(_, references)| !references.is_empty())
+                    .collect();
+                let fixed = self.fixed_tickets();
+                if !fixed.is_empty() {
+                    let offer = format!("Commits fix {}. Press F to resolve.", fixed.join(", "));
+                    self.notice = Some(match self.notice.take() {
+                        Some(notice) => format!("{} {}", offer, notice),
+                        None => offer,
+                    });
+                }
+            }
+            Err(error) => self.notice = Some(error.to_string()),
+        }
+        self.version_control = Some(version_control);
+        self
+    }
+
+    // 未完了になってから完了を示すコミットがあったチケットのID
+    fn fixed_tickets(&self) -> Vec<String> {
+        self.items
+            .iter()
+            .filter(|ticket| ticket.is_open())
+            .filter(|ticket| {
+                self.commits.iter().any(|(commit, references)| {
+                    commit_references::closes(ticket, commit.date, references)
+                })
+            })
+            .map(|ticket| ticket.id.clone())
+            .collect()
+    }
+
+    // 名簿に登録された現在のユーザーのID
+    fn current_member_id(&self) -> Option<String> {
+        self.header
+            .find_member(&self.current_user)
+            .map(|member| member.id.clone())
+    }
+
+    fn current_sprint(&self) -> Option<&Sprint> {
+        sprint_planning::current_sprint(&self.sprints, Utc::now().date_naive())
+    }
+
+    // 表示対象のチケットを木構造の順に並べたもの
+    fn visible_entries(&self) -> Vec<ticket_hierarchy::TreeEntry> {
+        let sprint_name = self
+            .current_sprint()
+            .filter(|_| self.current_sprint_only)
+            .map(|sprint| sprint.name.clone());
+        let mut indices = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, ticket)| match &sprint_name {
+                Some(name) => ticket.sprint.as_ref() == Some(name),
+                None => true,
+            })
+            .filter(|(_, ticket)| self.filter.matches(ticket))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        // 兄弟の間では優先度・手動の順位・作成日時の順に並べる
+        ticket_ordering::sort_indices(&self.items, &mut indices);
+        ticket_hierarchy::tree_order(&self.items, &indices, &self.collapsed)
+    }
+
+    // 表示対象のチケットのitems上のインデックス
+    fn visible_indices(&self) -> Vec<usize> {
+        self.visible_entries()
+            .into_iter()
+            .map(|entry| entry.index)
+            .collect()
+    }
+
+    fn selected_item_index(&self) -> Option<usize> {
+        self.state
+            .selected()
+            .and_then(|i| self.visible_indices().get(i).copied())
+    }
+
     fn next_row(&mut self) -> Result<()> {
+        let len = self.visible_indices().len();
+        if len == 0 {
+            return Ok(());
+        }
         let i = self.state.selected().unwrap_or(0);
         self.state
-            .select(Some(if i >= self.items.len() - 1 { 0 } else { i + 1 }));
+            .select(Some(if i >= len - 1 { 0 } else { i + 1 }));
         Ok(())
     }
 
     fn previous_row(&mut self) -> Result<()> {
+        let len = self.visible_indices().len();
+        if len == 0 {
+            return Ok(());
+        }
         let i = self.state.selected().unwrap_or(0);
         self.state
-            .select(Some(if i == 0 { self.items.len() - 1 } else { i - 1 }));
+            .select(Some(if i == 0 { len - 1 } else { i - 1 }));
         Ok(())
     }
 
     fn enter_edit_mode(&mut self) -> Result<()> {
-        if let Some(index) = self.state.selected() {
+        if let Some(index) = self.selected_item_index() {
             self.mode = AppMode::Edit;
             self.selected_ticket_index = Some(index);
+            self.selected_field = 0;
+        }
+        Ok(())
+    }
+
+    // ドメインのエラーは終了させずに通知として表示する
+    fn report(&mut self, result: Result<(), DomainError>) {
+        if let Err(error) = result {
+            self.notice = Some(error.to_string());
+        }
+    }
+
+    // force の場合はブロックされていても作業中に移行できる
+    fn cycle_status(&mut self, force: bool) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let id = self.items[index].id.clone();
+        let workflow = self.header.workflow();
+        let Some(new_status) = workflow.next_status(&self.items[index].status) else {
+            self.notice = Some(format!(
+                "No transition is allowed from {}.",
+                self.items[index].status.name()
+            ));
+            return Ok(());
+        };
+        let actor = self.current_member_id();
+        let result = status_transition::change_status(
+            &mut self.items,
+            &workflow,
+            &id,
+            new_status,
+            force,
+            actor.as_deref(),
+        )
+        .and_then(|spawned| {
+            self.repository.save_tickets(&self.items)?;
+            Ok(spawned)
+        });
+        match result {
+            Ok(Some(spawned)) => self.notice = Some(self.spawned_notice(&spawned)),
+            Ok(None) => {}
+            Err(error) => self.notice = Some(error.to_string()),
+        }
+        Ok(())
+    }
+
+    fn spawned_notice(&self, id: &str) -> String {
+        let due = self
+            .items
+            .iter()
+            .find(|ticket| ticket.id == id)
+            .and_then(|ticket| ticket.due)
+            .map(|due| due.to_string())
+            .unwrap_or_default();
+        format!("Created the next occurrence {} due {}", id, due)
+    }
+
+    fn edit_recurrence(&mut self) -> Result<()> {
+        if let Some(index) = self.selected_item_index() {
+            let recurrence = self.items[index]
+                .recurrence
+                .clone()
+                .map(String::from)
+                .unwrap_or_default();
+            self.begin_input(InputTarget::Recurrence, recurrence);
+        }
+        Ok(())
+    }
+
+    fn toggle_timer(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let now = Utc::now();
+        if self.items[index].is_timer_running() {
+            self.items[index].stop_timer(now);
+        } else {
+            let member = self.current_member_id();
+            self.items[index].start_timer(member, now);
+        }
+        let result = self.repository.save_tickets(&self.items);
+        self.report(result);
+        Ok(())
+    }
+
+    fn log_time(&mut self) -> Result<()> {
+        if self.selected_item_index().is_some() {
+            self.begin_input(InputTarget::TimeLog, String::new());
+        }
+        Ok(())
+    }
+
+    // 詳細画面で表示しているチケットにコメントを追記する
+    fn add_comment(&mut self) -> Result<()> {
+        if matches!(self.mode, AppMode::Edit) && self.selected_ticket_index.is_some() {
+            self.begin_input(InputTarget::Comment, String::new());
+        }
+        Ok(())
+    }
+
+    fn next_field(&mut self) -> Result<()> {
+        if matches!(self.mode, AppMode::Edit) && !self.header.fields.is_empty() {
+            self.selected_field = (self.selected_field + 1) % self.header.fields.len();
+        }
+        Ok(())
+    }
+
+    // 真偽値は切り替え、列挙値は次の選択肢に進め、それ以外は入力欄で編集する
+    fn edit_field(&mut self) -> Result<()> {
+        if !matches!(self.mode, AppMode::Edit) {
+            return Ok(());
+        }
+        let (Some(index), Some(definition)) = (
+            self.selected_ticket_index,
+            self.header.fields.get(self.selected_field).cloned(),
+        ) else {
+            return Ok(());
+        };
+        let current = self.items[index].fields.get(&definition.name).cloned();
+        let next = match (definition.kind, &current) {
+            (FieldType::Bool, Some(FieldValue::Bool(value))) => (!value).to_string(),
+            (FieldType::Bool, _) => "true".to_string(),
+            (FieldType::Enum, _) => {
+                let position = current.and_then(|value| {
+                    definition
+                        .options
+                        .iter()
+                        .position(|option| value.to_string() == *option)
+                });
+                // 最後の選択肢の次は未設定に戻る
+                match position {
+                    Some(i) => definition.options.get(i + 1).cloned().unwrap_or_default(),
+                    None => definition.options.first().cloned().unwrap_or_default(),
+                }
+            }
+            _ => {
+                let value = current.map(|value| value.to_string()).unwrap_or_default();
+                self.begin_input(InputTarget::Field(definition.name.clone()), value);
+                return Ok(());
+            }
+        };
+        let result = self.items[index]
+            .set_field(&definition, &next)
+            .and_then(|_| self.repository.save_tickets(&self.items));
+        self.report(result);
+        Ok(())
+    }
+
+    fn toggle_collapse(&mut self) -> Result<()> {
+        if let Some(index) = self.selected_item_index() {
+            let id = self.items[index].id.clone();
+            if !self.collapsed.remove(&id) {
+                self.collapsed.insert(id);
+            }
+        }
+        Ok(())
+    }
+
+    fn begin_input(&mut self, target: InputTarget, initial: String) {
+        self.input = initial;
+        self.input_target = Some(target);
+        self.mode = AppMode::Input;
+    }
+
+    fn edit_tags(&mut self) -> Result<()> {
+        if let Some(index) = self.selected_item_index() {
+            let tags = self.items[index].tags.join(", ");
+            self.begin_input(InputTarget::Tags, tags);
+        }
+        Ok(())
+    }
+
+    fn edit_due_date(&mut self) -> Result<()> {
+        if let Some(index) = self.selected_item_index() {
+            let due = self.items[index]
+                .due
+                .map(|due| due.to_string())
+                .unwrap_or_default();
+            self.begin_input(InputTarget::DueDate, due);
+        }
+        Ok(())
+    }
+
+    fn edit_tag_filter(&mut self) -> Result<()> {
+        let filter = self
+            .filter
+            .tags
+            .iter()
+            .cloned()
+            .chain(self.filter.conditions.iter().map(String::from))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.begin_input(InputTarget::TagFilter, filter);
+        Ok(())
+    }
+
+    fn is_input_mode(&self) -> bool {
+        matches!(self.mode, AppMode::Input)
+    }
+
+    fn input_char(&mut self, c: char) -> Result<()> {
+        self.input.push(c);
+        Ok(())
+    }
+
+    fn input_backspace(&mut self) -> Result<()> {
+        self.input.pop();
+        Ok(())
+    }
+
+    fn cancel_input(&mut self) -> Result<()> {
+        self.input.clear();
+        self.mode = self
+            .input_target
+            .take()
+            .map_or(AppMode::Normal, |target| target.return_mode());
+        Ok(())
+    }
+
+    fn submit_input(&mut self) -> Result<()> {
+        let input = std::mem::take(&mut self.input);
+        let target = self.input_target.take();
+        match &target {
+            Some(InputTarget::Tags) => {
+                if let Some(index) = self.selected_item_index() {
+                    let tags = parse_tags(&input);
+                    let unregistered: Vec<&String> = tags
+                        .iter()
+                        .filter(|tag| !self.header.is_registered_tag(tag))
+                        .collect();
+                    self.notice = (!unregistered.is_empty()).then(|| {
+                        let names: Vec<&str> =
+                            unregistered.iter().map(|tag| tag.as_str()).collect();
+                        format!("Unregistered tags: {}", names.join(", "))
+                    });
+                    self.items[index].set_tags(tags);
+                    let result = self.repository.save_tickets(&self.items);
+                    self.report(result);
+                }
+            }
+            Some(InputTarget::DueDate) => {
+                if let Some(index) = self.selected_item_index() {
+                    match parse_date(&input, Utc::now().date_naive()) {
+                        Ok(due) => {
+                            self.items[index].due = due;
+                            let result = self.repository.save_tickets(&self.items);
+                            self.report(result);
+                        }
+                        Err(message) => self.notice = Some(message),
+                    }
+                }
+            }
+            Some(InputTarget::Recurrence) => {
+                if let Some(index) = self.selected_item_index() {
+                    let recurrence = match input.trim() {
+                        "" | "none" => Ok(None),
+                        input => Recurrence::parse(input).map(Some),
+                    };
+                    let result = recurrence.and_then(|recurrence| {
+                        self.items[index].recurrence = recurrence;
+                        self.repository.save_tickets(&self.items)
+                    });
+                    self.report(result);
+                }
+            }
+            Some(InputTarget::TimeLog) => {
+                if let Some(index) = self.selected_item_index() {
+                    // 先頭の語を作業時間、残りをメモとして扱う
+                    let (duration, note) = input.trim().split_once(' ').unwrap_or((&input, ""));
+                    match parse_duration(duration) {
+                        Ok(duration) => {
+                            let member = self.current_member_id();
+                            let result = self.items[index]
+                                .log_time(duration, member, note.trim().to_string(), Utc::now())
+                                .and_then(|_| self.repository.save_tickets(&self.items));
+                            self.report(result);
+                        }
+                        Err(message) => self.notice = Some(message),
+                    }
+                }
+            }
+            Some(InputTarget::Comment) => {
+                if let Some(index) = self.selected_ticket_index {
+                    let author = self.header.author_name(&self.current_user);
+                    let result = self.items[index]
+                        .add_comment(author, &input, Utc::now())
+                        .and_then(|_| self.repository.save_tickets(&self.items));
+                    self.report(result);
+                }
+            }
+            Some(InputTarget::Field(name)) => {
+                if let (Some(index), Some(definition)) =
+                    (self.selected_ticket_index, self.header.field(name))
+                {
+                    // 日付は "tomorrow" や "+3d" などの入力も受け付ける
+                    let value = match definition.kind {
+                        FieldType::Date => parse_date(&input, Utc::now().date_naive())
+                            .map(|date| date.map(|date| date.to_string()).unwrap_or_default()),
+                        _ => Ok(input),
+                    };
+                    match value {
+                        Ok(value) => {
+                            let result = self.items[index]
+                                .set_field(definition, &value)
+                                .and_then(|_| self.repository.save_tickets(&self.items));
+                            self.report(result);
+                        }
+                        Err(message) => self.notice = Some(message),
+                    }
+                }
+            }
+            Some(InputTarget::TagFilter) => {
+                // 演算子を含む語はフィールドの条件、それ以外はタグとして扱う
+                let mut tags = Vec::new();
+                let mut conditions = Vec::new();
+                for token in parse_tags(&input) {
+                    match parse_condition(&token) {
+                        Ok(Some(condition)) => conditions.push(condition),
+                        Ok(None) => tags.push(token),
+                        Err(message) => self.notice = Some(message),
+                    }
+                }
+                self.filter.tags = tags;
+                self.filter.conditions = conditions;
+                self.state.select(Some(0));
+            }
+            None => {}
+        }
+        self.mode = target.map_or(AppMode::Normal, |target| target.return_mode());
+        Ok(())
+    }
+
+    fn toggle_mine_filter(&mut self) -> Result<()> {
+        if self.filter.assignee.is_some() {
+            self.filter.assignee = None;
+        } else {
+            match self.header.find_member(&self.current_user) {
+                Some(member) => self.filter.assignee = Some(member.id.clone()),
+                None => {
+                    self.notice = Some("The current user is not in the member list.".to_string())
+                }
+            }
+        }
+        self.state.select(Some(0));
+        Ok(())
+    }
+
+    fn cycle_priority(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let id = self.items[index].id.clone();
+        self.items[index].priority = self.items[index].priority.next();
+        // 優先度が変わると手動の順位は意味を持たないため未設定に戻す
+        self.items[index].rank = 0;
+        let result = self.repository.save_tickets(&self.items);
+        self.report(result);
+        self.select_ticket(&id);
+        Ok(())
+    }
+
+    // 選択中のチケットを同じ優先度の兄弟の中で上下に移動する
+    fn move_rank(&mut self, up: bool) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let id = self.items[index].id.clone();
+        let result =
+            ticket_ordering::move_within_priority(&mut self.items, &id, up).and_then(|moved| {
+                if moved {
+                    self.repository.save_tickets(&self.items)
+                } else {
+                    Ok(())
+                }
+            });
+        self.report(result);
+        self.select_ticket(&id);
+        Ok(())
+    }
+
+    // 並び順が変わった後も同じチケットを選択したままにする
+    fn select_ticket(&mut self, id: &str) {
+        if let Some(position) = self
+            .visible_indices()
+            .iter()
+            .position(|&i| self.items[i].id == id)
+        {
+            self.state.select(Some(position));
+        }
+    }
+
+    // 選択中のチケットの見積もりをファイルのスケールの順に切り替える
+    fn cycle_estimate(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let levels = self.header.estimation_levels();
+        let next = levels
+            .iter()
+            .position(|level| level.label() == self.items[index].level.label())
+            .map_or(0, |position| (position + 1) % levels.len());
+        self.items[index].level = levels[next].clone();
+        let result = self.repository.save_tickets(&self.items);
+        self.report(result);
+        Ok(())
+    }
+
+    // 選択中のチケットの担当者を 未割当 -> 名簿のメンバー の順に切り替える
+    fn cycle_assignee(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let mut choices: Vec<Option<String>> = vec![None];
+        choices.extend(
+            self.header
+                .members
+                .iter()
+                .map(|member| Some(member.id.clone())),
+        );
+        let position = choices
+            .iter()
+            .position(|choice| *choice == self.items[index].assignee)
+            .unwrap_or(0);
+        self.items[index].assignee = choices[(position + 1) % choices.len()].clone();
+        let result = self.repository.save_tickets(&self.items);
+        self.report(result);
+        Ok(())
+    }
+
+    // 選択中のチケットを次のプロジェクトのファイルに移す
+    fn cycle_project(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        if self.projects.len() < 2 {
+            self.notice = Some("Open several files to move tickets between projects.".to_string());
+            return Ok(());
+        }
+        let position = self
+            .projects
+            .iter()
+            .position(|project| Some(project) == self.items[index].project.as_ref())
+            .unwrap_or(0);
+        let project = self.projects[(position + 1) % self.projects.len()].clone();
+        self.items[index].project = Some(project.clone());
+        let result = self.repository.save_tickets(&self.items);
+        self.notice = Some(format!("{} -> {}", self.items[index].id, project));
+        self.report(result);
+        Ok(())
+    }
+
+    // コミットで修正済みとされたチケットを完了にする。子チケットが先に完了するまで親は繰り返し試す
+    fn resolve_fixed(&mut self) -> Result<()> {
+        let mut pending = self.fixed_tickets();
+        if pending.is_empty() {
+            self.notice = Some("No open tickets are fixed by a commit.".to_string());
+            return Ok(());
+        }
+        let workflow = self.header.workflow();
+        let actor = self.current_member_id();
+        let mut resolved = Vec::new();
+        let mut last_error = None;
+        loop {
+            let before = pending.len();
+            pending.retain(|id| {
+                let Some(ticket) = self.items.iter().find(|ticket| ticket.id == *id) else {
+                    return false;
+                };
+                let Some(status) = workflow.resolved_status(&ticket.status) else {
+                    last_error = Some(DomainError::TransitionNotAllowed(
+                        id.clone(),
+                        ticket.status.clone().into(),
+                        "a done status".to_string(),
+                    ));
+                    return true;
+                };
+                match status_transition::change_status(
+                    &mut self.items,
+                    &workflow,
+                    id,
+                    status,
+                    false,
+                    actor.as_deref(),
+                ) {
+                    Ok(_) => {
+                        resolved.push(id.clone());
+                        false
+                    }
+                    Err(error) => {
+                        last_error = Some(error);
+                        true
+                    }
+                }
+            });
+            if pending.is_empty() || pending.len() == before {
+                break;
+            }
+        }
+        let mut messages = Vec::new();
+        if !resolved.is_empty() {
+            messages.push(format!("Resolved {}.", resolved.join(", ")));
+            if let Err(error) = self.repository.save_tickets(&self.items) {
+                messages.push(error.to_string());
+            }
         }
+        if let (false, Some(error)) = (pending.is_empty(), last_error) {
+            messages.push(error.to_string());
+        }
+        self.notice = Some(messages.join(" "));
+        Ok(())
+    }
+
+    // 選択中のチケットのIDとタイトルから名前を付けたブランチを作って切り替える
+    fn create_branch(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let Some(version_control) = &self.version_control else {
+            self.notice = Some("The ticket file is not in a git repository.".to_string());
+            return Ok(());
+        };
+        let name = commit_references::branch_name(&self.items[index]);
+        self.notice = Some(match version_control.create_branch(&name) {
+            Ok(()) => format!("Switched to a new branch {}", name),
+            Err(error) => error.to_string(),
+        });
+        Ok(())
+    }
+
+    fn toggle_sprint_filter(&mut self) -> Result<()> {
+        self.current_sprint_only = !self.current_sprint_only;
+        self.state.select(Some(0));
         Ok(())
     }
 
+    // 選択中のチケットの所属スプリントを 未割当 -> 未クローズのスプリント の順に切り替える
+    fn cycle_ticket_sprint(&mut self) -> Result<()> {
+        let Some(index) = self.selected_item_index() else {
+            return Ok(());
+        };
+        let mut choices: Vec<Option<String>> = vec![None];
+        choices.extend(
+            self.sprints
+                .iter()
+                .filter(|sprint| !sprint.closed)
+                .map(|sprint| Some(sprint.name.clone())),
+        );
+        let position = choices
+            .iter()
+            .position(|choice| *choice == self.items[index].sprint)
+            .unwrap_or(0);
+        self.items[index].sprint = choices[(position + 1) % choices.len()].clone();
+        self.notice = Some(format!(
+            "{} -> {}",
+            self.items[index].id,
+            self.items[index].sprint.as_deref().unwrap_or("(no sprint)")
+        ));
+        let result = self.repository.save_tickets(&self.items);
+        self.report(result);
+        Ok(())
+    }
+
+    fn close_current_sprint(&mut self) -> Result<()> {
+        let today = Utc::now().date_naive();
+        let Some(name) = sprint_planning::sprint_to_close(&self.sprints, today)
+            .map(|sprint| sprint.name.clone())
+        else {
+            self.notice = Some("No started sprint to close.".to_string());
+            return Ok(());
+        };
+        let result = sprint_planning::close_sprint(&mut self.sprints, &mut self.items, &name)
+            .and_then(|next_name| {
+                self.repository.save_sprints(&self.sprints)?;
+                self.repository.save_tickets(&self.items)?;
+                Ok(next_name)
+            });
+        match result {
+            Ok(next_name) => {
+                self.notice = Some(format!(
+                    "Closed {}. Unfinished tickets moved to {}.",
+                    name, next_name
+                ));
+                self.state.select(Some(0));
+            }
+            Err(error) => self.notice = Some(error.to_string()),
+        }
+        Ok(())
+    }
+
+    fn table_title(&self) -> String {
+        let mut title = self.sprint_title();
+        if !self.filter.tags.is_empty() {
+            title = format!("{} [tags: {}]", title, self.filter.tags.join(", "));
+        }
+        if !self.filter.conditions.is_empty() {
+            let conditions: Vec<String> = self.filter.conditions.iter().map(String::from).collect();
+            title = format!("{} [query: {}]", title, conditions.join(" "));
+        }
+        if let Some(assignee) = &self.filter.assignee {
+            title = format!("{} [assignee: {}]", title, assignee);
+        }
+        title
+    }
+
+    fn sprint_title(&self) -> String {
+        match self.current_sprint().filter(|_| self.current_sprint_only) {
+            Some(sprint) if sprint.goal.is_empty() => format!(
+                "Ticket List - {} ({} ~ {})",
+                sprint.name, sprint.start, sprint.end
+            ),
+            Some(sprint) => format!(
+                "Ticket List - {} ({} ~ {}) {}",
+                sprint.name, sprint.start, sprint.end, sprint.goal
+            ),
+            None => "Ticket List".to_string(),
+        }
+    }
+
+    fn enter_chart_mode(&mut self) -> Result<()> {
+        self.mode = AppMode::Chart;
+        Ok(())
+    }
+
+    fn enter_agenda_mode(&mut self) -> Result<()> {
+        self.mode = AppMode::Agenda;
+        Ok(())
+    }
+
+    fn enter_board_mode(&mut self) -> Result<()> {
+        self.mode = AppMode::Board;
+        Ok(())
+    }
+
+    // ワークフローの宣言順にステータスごとの列を作る。宣言されていないステータスは末尾の列にまとめる
+    fn build_board(&self, tickets: Vec<TicketDTO>) -> Vec<BoardColumnDTO> {
+        let workflow = self.header.workflow();
+        let mut columns: Vec<BoardColumnDTO> = workflow
+            .statuses
+            .iter()
+            .map(|status| BoardColumnDTO {
+                status: status.name.clone(),
+                category: status.category.into(),
+                tickets: Vec::new(),
+            })
+            .collect();
+        for ticket in tickets {
+            match columns
+                .iter_mut()
+                .find(|column| column.status == ticket.status)
+            {
+                Some(column) => column.tickets.push(ticket),
+                None => columns.push(BoardColumnDTO {
+                    status: ticket.status.clone(),
+                    category: ticket.status_category.clone(),
+                    tickets: vec![ticket],
+                }),
+            }
+        }
+        columns
+    }
+
+    // 期日のある未完了チケットを区分ごとに期日順で並べる(スプリントでは絞り込まない)
+    fn build_agenda(&self, today: NaiveDate) -> Vec<AgendaGroupDTO> {
+        let mut dated: Vec<&Ticket> = self
+            .items
+            .iter()
+            .filter(|ticket| self.filter.matches(ticket) && ticket.due_bucket(today).is_some())
+            .collect();
+        dated.sort_by_key(|ticket| ticket.due);
+
+        DueBucket::ALL
+            .iter()
+            .map(|bucket| AgendaGroupDTO {
+                title: (*bucket).into(),
+                tickets: dated
+                    .iter()
+                    .filter(|ticket| ticket.due_bucket(today) == Some(*bucket))
+                    .map(|ticket| TicketDTO::from(*ticket))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn enter_normal_mode(&mut self) -> Result<()> {
+        self.mode = AppMode::Normal;
+        Ok(())
+    }
+
+    fn cycle_chart_window(&mut self) -> Result<()> {
+        self.chart_window = self.chart_window.next();
+        Ok(())
+    }
+
+    fn build_chart_dto(&self, now: DateTime<Utc>) -> ChartDTO {
+        let start = ticket_statistics::window_start(&self.items, self.chart_window, now);
+        let elapsed_days = |at: DateTime<Utc>| (at - start).num_seconds() as f64 / 86_400.0;
+
+        let burndown = ticket_statistics::burndown(&self.items, start, now)
+            .into_iter()
+            .map(|point| (elapsed_days(point.at), point.remaining_points as f64))
+            .collect();
+
+        let velocity = ticket_statistics::velocity(&self.items, start, now)
+            .into_iter()
+            .map(|bucket| {
+                (
+                    bucket.week_start.format("%m/%d").to_string(),
+                    bucket.resolved_points as u64,
+                )
+            })
+            .collect();
+
+        let mut done_flow = Vec::new();
+        let mut in_progress_flow = Vec::new();
+        let mut todo_flow = Vec::new();
+        for point in ticket_statistics::cumulative_flow(&self.items, start, now) {
+            let x = elapsed_days(point.at);
+            done_flow.push((x, point.done as f64));
+            in_progress_flow.push((x, (point.done + point.in_progress) as f64));
+            todo_flow.push((x, (point.done + point.in_progress + point.todo) as f64));
+        }
+
+        let workload = ticket_statistics::workload_by_assignee(&self.items, &self.header.members)
+            .into_iter()
+            .map(|workload| {
+                (
+                    workload.assignee.unwrap_or_else(|| "(none)".to_string()),
+                    workload.open_points as u64,
+                )
+            })
+            .collect();
+
+        let middle = start + (now - start) / 2;
+        ChartDTO {
+            window: self.chart_window.into(),
+            x_labels: [start, middle, now]
+                .iter()
+                .map(|at| at.format("%Y-%m-%d").to_string())
+                .collect(),
+            x_max: elapsed_days(now),
+            burndown,
+            velocity,
+            done_flow,
+            in_progress_flow,
+            todo_flow,
+            workload,
+        }
+    }
+
+    // 詳細画面で表示するチケット
+    fn selected_ticket_dto(&self) -> Option<TicketDTO> {
+        self.selected_ticket_index.map(|i| TicketDTO {
+            tags: TagDTO::list(&self.items[i].tags, &self.header),
+            fields: FieldDTO::list(&self.items[i], &self.header),
+            commits: self
+                .commits
+                .iter()
+                .filter_map(|(commit, references)| {
+                    let reference = references
+                        .iter()
+                        .find(|reference| reference.id == self.items[i].id)?;
+                    Some(CommitDTO {
+                        closes: reference.closes,
+                        ..commit.clone()
+                    })
+                })
+                .collect(),
+            ..TicketDTO::from(&self.items[i])
+        })
+    }
+
     fn draw(&mut self, frame: &mut Frame) -> Result<()> {
         let rects =
             Layout::vertical([Constraint::Min(5), Constraint::Length(4)]).split(frame.area());
 
         // DTOに変換
         let ticket_dtos: Vec<TicketDTO> = self
-            .items
-            .iter()
-            .map(|ticket| TicketDTO {
-                id: ticket.id.clone(),
-                level: ticket.level.clone().into(),
-                title: ticket.title.clone(),
-                status: ticket.status.clone().into(),
-                created_at: ticket.created_at.clone(),
-                resolved_at: ticket.resolved_at.clone(),
+            .visible_entries()
+            .into_iter()
+            .map(|entry| {
+                let ticket = &self.items[entry.index];
+                TicketDTO {
+                    depth: entry.depth,
+                    points: ticket_hierarchy::rolled_up_points(&self.items, &ticket.id),
+                    has_children: ticket_hierarchy::children_of(&self.items, &ticket.id)
+                        .next()
+                        .is_some(),
+                    collapsed: self.collapsed.contains(&ticket.id),
+                    open_blockers: ticket_dependencies::open_blockers(&self.items, &ticket.id)
+                        .len(),
+                    // 完了済みのチケットは何もブロックしていないとみなす
+                    blocking: ticket_dependencies::blocks(&self.items, &ticket.id)
+                        .filter(|blocked| ticket.is_open() && blocked.is_open())
+                        .count(),
+                    tags: TagDTO::list(&ticket.tags, &self.header),
+                    ..TicketDTO::from(ticket)
+                }
             })
             .collect();
+        let notice = self.notice.as_deref();
 
         match self.mode {
             AppMode::Normal => {
+                self.output_port.draw_table(
+                    frame,
+                    rects[0],
+                    &self.table_title(),
+                    self.state.selected(),
+                    &ticket_dtos,
+                );
+                self.output_port
+                    .draw_footer(frame, rects[1], &self.mode, notice);
+            }
+            AppMode::Input => {
+                if matches!(
+                    self.input_target,
+                    Some(InputTarget::Comment | InputTarget::Field(_))
+                ) {
+                    let selected_ticket = self.selected_ticket_dto();
+                    self.output_port.draw_detail(
+                        frame,
+                        rects[0],
+                        selected_ticket.as_ref(),
+                        self.selected_field,
+                    );
+                } else {
+                    self.output_port.draw_table(
+                        frame,
+                        rects[0],
+                        &self.table_title(),
+                        self.state.selected(),
+                        &ticket_dtos,
+                    );
+                }
+                let prompt = self
+                    .input_target
+                    .as_ref()
+                    .map(|target| target.prompt())
+                    .unwrap_or_default();
                 self.output_port
-                    .draw_table(frame, rects[0], self.state.selected(), &ticket_dtos);
-                self.output_port.draw_footer(frame, rects[1]);
+                    .draw_input(frame, rects[1], &prompt, &self.input);
             }
             AppMode::Edit => {
-                let selected_ticket = self
-                    .selected_ticket_index
-                    .and_then(|i| Some(self.items[i].title.as_str()));
+                let selected_ticket = self.selected_ticket_dto();
+                self.output_port.draw_detail(
+                    frame,
+                    rects[0],
+                    selected_ticket.as_ref(),
+                    self.selected_field,
+                );
+                self.output_port
+                    .draw_footer(frame, rects[1], &self.mode, notice);
+            }
+            AppMode::Agenda => {
+                let agenda = self.build_agenda(Utc::now().date_naive());
+                self.output_port.draw_agenda(frame, rects[0], &agenda);
+                self.output_port
+                    .draw_footer(frame, rects[1], &self.mode, notice);
+            }
+            AppMode::Board => {
+                let title = self.table_title().replacen("Ticket List", "Board", 1);
+                let columns = self.build_board(ticket_dtos);
                 self.output_port
-                    .draw_edit_form(frame, rects[0], selected_ticket);
+                    .draw_board(frame, rects[0], &title, &columns);
+                self.output_port
+                    .draw_footer(frame, rects[1], &self.mode, notice);
+            }
+            AppMode::Chart => {
+                let chart = self.build_chart_dto(Utc::now());
+                self.output_port.draw_charts(frame, rects[0], &chart);
+                self.output_port
+                    .draw_footer(frame, rects[1], &self.mode, notice);
             }
         }
 
@@ -113,6 +1187,134 @@ impl<R: TicketRepository, O: TerminalOutputPort> TerminalInputPort for TerminalI
         self.enter_edit_mode()
     }
 
+    fn enter_chart_mode(&mut self) -> Result<()> {
+        self.enter_chart_mode()
+    }
+
+    fn enter_normal_mode(&mut self) -> Result<()> {
+        self.enter_normal_mode()
+    }
+
+    fn cycle_chart_window(&mut self) -> Result<()> {
+        self.cycle_chart_window()
+    }
+
+    fn toggle_sprint_filter(&mut self) -> Result<()> {
+        self.toggle_sprint_filter()
+    }
+
+    fn toggle_mine_filter(&mut self) -> Result<()> {
+        self.toggle_mine_filter()
+    }
+
+    fn cycle_assignee(&mut self) -> Result<()> {
+        self.cycle_assignee()
+    }
+
+    fn cycle_estimate(&mut self) -> Result<()> {
+        self.cycle_estimate()
+    }
+
+    fn cycle_priority(&mut self) -> Result<()> {
+        self.cycle_priority()
+    }
+
+    fn move_rank(&mut self, up: bool) -> Result<()> {
+        self.move_rank(up)
+    }
+
+    fn edit_tags(&mut self) -> Result<()> {
+        self.edit_tags()
+    }
+
+    fn edit_tag_filter(&mut self) -> Result<()> {
+        self.edit_tag_filter()
+    }
+
+    fn edit_due_date(&mut self) -> Result<()> {
+        self.edit_due_date()
+    }
+
+    fn enter_agenda_mode(&mut self) -> Result<()> {
+        self.enter_agenda_mode()
+    }
+
+    fn enter_board_mode(&mut self) -> Result<()> {
+        self.enter_board_mode()
+    }
+
+    fn is_input_mode(&self) -> bool {
+        self.is_input_mode()
+    }
+
+    fn input_char(&mut self, c: char) -> Result<()> {
+        self.input_char(c)
+    }
+
+    fn input_backspace(&mut self) -> Result<()> {
+        self.input_backspace()
+    }
+
+    fn submit_input(&mut self) -> Result<()> {
+        self.submit_input()
+    }
+
+    fn cancel_input(&mut self) -> Result<()> {
+        self.cancel_input()
+    }
+
+    fn cycle_status(&mut self, force: bool) -> Result<()> {
+        self.cycle_status(force)
+    }
+
+    fn toggle_collapse(&mut self) -> Result<()> {
+        self.toggle_collapse()
+    }
+
+    fn add_comment(&mut self) -> Result<()> {
+        self.add_comment()
+    }
+
+    fn next_field(&mut self) -> Result<()> {
+        self.next_field()
+    }
+
+    fn edit_field(&mut self) -> Result<()> {
+        self.edit_field()
+    }
+
+    fn toggle_timer(&mut self) -> Result<()> {
+        self.toggle_timer()
+    }
+
+    fn edit_recurrence(&mut self) -> Result<()> {
+        self.edit_recurrence()
+    }
+
+    fn log_time(&mut self) -> Result<()> {
+        self.log_time()
+    }
+
+    fn cycle_ticket_sprint(&mut self) -> Result<()> {
+        self.cycle_ticket_sprint()
+    }
+
+    fn close_current_sprint(&mut self) -> Result<()> {
+        self.close_current_sprint()
+    }
+
+    fn cycle_project(&mut self) -> Result<()> {
+        self.cycle_project()
+    }
+
+    fn resolve_fixed(&mut self) -> Result<()> {
+        self.resolve_fixed()
+    }
+
+    fn create_branch(&mut self) -> Result<()> {
+        self.create_branch()
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         self.draw(frame).unwrap();
     }
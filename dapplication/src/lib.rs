@@ -2,3 +2,4 @@ pub mod dtos;
 pub mod input_ports;
 pub mod interactors;
 pub mod output_ports;
+pub mod parsers;
@@ -1 +1,6 @@
+pub mod cli_output_port;
+pub mod code_scanner;
 pub mod terminal_output_port;
+pub mod ticket_exporter;
+pub mod ticket_importer;
+pub mod version_control;
@@ -0,0 +1,15 @@
+use crate::dtos::graph_dto::{GraphDTO, GraphFormat};
+use crate::dtos::ticket_dto::TicketDTO;
+use crate::dtos::timesheet_dto::TimesheetDTO;
+use crate::dtos::workload_dto::WorkloadDTO;
+
+pub trait CliOutputPort {
+    fn present_tickets(&self, tickets: &[TicketDTO]);
+    fn present_warnings(&self, warnings: &[String]);
+    fn present_workload(&self, workload: &[WorkloadDTO]);
+    fn present_timesheet(&self, timesheet: &TimesheetDTO);
+    fn present_graph(&self, graph: &GraphDTO, format: GraphFormat);
+    fn present_message(&self, message: &str);
+    // 書き出した文書をそのまま出力する
+    fn present_export(&self, content: &str);
+}
@@ -0,0 +1,10 @@
+use crate::dtos::code_marker_dto::CodeMarkerDTO;
+use color_eyre::Result;
+
+// ソースコードを走査して TODO などのコメントを集める
+pub trait CodeScanner {
+    // ファイル順・行順に並べる
+    fn scan(&self) -> Result<Vec<CodeMarkerDTO>>;
+    // 走査の範囲に含まれるパスか。範囲外のコメントから作ったチケットは解決しない
+    fn covers(&self, path: &str) -> bool;
+}
@@ -1,4 +1,8 @@
+use crate::dtos::agenda_dto::AgendaGroupDTO;
+use crate::dtos::board_dto::BoardColumnDTO;
+use crate::dtos::chart_dto::ChartDTO;
 use crate::dtos::ticket_dto::TicketDTO;
+use ddomain::value_objects::app_mode::AppMode;
 use ratatui::{layout::Rect, Frame};
 
 pub trait TerminalOutputPort {
@@ -6,9 +10,21 @@ pub trait TerminalOutputPort {
         &self,
         frame: &mut Frame,
         area: Rect,
+        title: &str,
         selected_index: Option<usize>,
-        tickets: &Vec<TicketDTO>,
+        tickets: &[TicketDTO],
     );
-    fn draw_footer(&self, frame: &mut Frame, area: Rect);
-    fn draw_edit_form(&self, frame: &mut Frame, area: Rect, selected_ticket: Option<&str>);
+    fn draw_footer(&self, frame: &mut Frame, area: Rect, mode: &AppMode, notice: Option<&str>);
+    // selected_field は詳細画面で選択中のカスタムフィールドの位置
+    fn draw_detail(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        selected_ticket: Option<&TicketDTO>,
+        selected_field: usize,
+    );
+    fn draw_input(&self, frame: &mut Frame, area: Rect, prompt: &str, value: &str);
+    fn draw_agenda(&self, frame: &mut Frame, area: Rect, agenda: &[AgendaGroupDTO]);
+    fn draw_board(&self, frame: &mut Frame, area: Rect, title: &str, columns: &[BoardColumnDTO]);
+    fn draw_charts(&self, frame: &mut Frame, area: Rect, chart: &ChartDTO);
 }
@@ -0,0 +1,7 @@
+use crate::dtos::export_dto::ExportDTO;
+use color_eyre::Result;
+
+// チケットの一覧を外部向けの文書に変換する
+pub trait TicketExporter {
+    fn export(&self, export: &ExportDTO) -> Result<String>;
+}
@@ -0,0 +1,7 @@
+use crate::dtos::import_dto::ImportedTicketDTO;
+use color_eyre::Result;
+
+// 外部のツールが書き出したファイルからチケットを読み込む
+pub trait TicketImporter {
+    fn read(&self) -> Result<Vec<ImportedTicketDTO>>;
+}
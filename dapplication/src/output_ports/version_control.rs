@@ -0,0 +1,10 @@
+use crate::dtos::commit_dto::CommitDTO;
+use color_eyre::Result;
+
+// チケットファイルを管理しているローカルのリポジトリ(ネットワークには接続しない)
+pub trait VersionControl {
+    // 現在のブランチの履歴を新しい順に返す
+    fn commits(&self) -> Result<Vec<CommitDTO>>;
+    // ブランチを作って切り替える
+    fn create_branch(&self, name: &str) -> Result<()>;
+}
@@ -0,0 +1,3 @@
+pub mod date_parser;
+pub mod duration_parser;
+pub mod query_parser;
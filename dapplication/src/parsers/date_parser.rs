@@ -0,0 +1,135 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+
+// 期日などの日付の入力を解釈する。対応する書式:
+//   today / tomorrow / yesterday
+//   mon..sun, monday..sunday (今日より後の直近の曜日)
+//   +3d / -2w / +1m (今日からの相対指定)
+//   2024-05-01 / 05-01 (年を省略した場合は今日以降の直近の日付)
+// 空文字列と "none" は日付なしを表す Ok(None) を返す
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<Option<NaiveDate>, String> {
+    let input = input.trim().to_lowercase();
+    let date = match input.as_str() {
+        "" | "none" => return Ok(None),
+        "today" => Some(today),
+        "tomorrow" | "tmr" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        _ => parse_weekday(&input, today)
+            .or_else(|| parse_relative(&input, today))
+            .or_else(|| parse_absolute(&input, today)),
+    };
+    date.map(Some)
+        .ok_or_else(|| format!("Could not understand the date '{}'.", input))
+}
+
+fn parse_weekday(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let weekday: Weekday = input.parse().ok()?;
+    let days_ahead =
+        (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    // 同じ曜日の場合は翌週
+    let days_ahead = if days_ahead == 0 { 7 } else { days_ahead };
+    Some(today + Duration::days(days_ahead as i64))
+}
+
+fn parse_relative(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (sign, rest) = match input.chars().next()? {
+        '+' => (1, &input[1..]),
+        '-' => (-1, &input[1..]),
+        _ => return None,
+    };
+    let unit = rest.chars().last()?;
+    let digits = &rest[..rest.len() - unit.len_utf8()];
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // 桁が多すぎる場合や日付の範囲を超える場合は解釈できない入力として扱う
+    let amount: i64 = digits.parse().ok()?;
+    match unit {
+        'd' => today.checked_add_signed(Duration::try_days(sign * amount)?),
+        'w' => today.checked_add_signed(Duration::try_weeks(sign * amount)?),
+        'm' => {
+            let months = Months::new(u32::try_from(amount).ok()?);
+            if sign > 0 {
+                today.checked_add_months(months)
+            } else {
+                today.checked_sub_months(months)
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_absolute(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Some(date);
+    }
+    let (month, day) = input.split_once(['-', '/'])?;
+    let (month, day): (u32, u32) = (month.parse().ok()?, day.parse().ok()?);
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    // 2026-10-18 は日曜日
+    fn parse(input: &str) -> Result<Option<NaiveDate>, String> {
+        parse_date(input, date("2026-10-18"))
+    }
+
+    #[test]
+    fn keywords_and_weekdays() {
+        assert_eq!(parse(""), Ok(None));
+        assert_eq!(parse("None"), Ok(None));
+        assert_eq!(parse("today"), Ok(Some(date("2026-10-18"))));
+        assert_eq!(parse("tmr"), Ok(Some(date("2026-10-19"))));
+        assert_eq!(parse("yesterday"), Ok(Some(date("2026-10-17"))));
+        assert_eq!(parse("fri"), Ok(Some(date("2026-10-23"))));
+        assert_eq!(parse("sunday"), Ok(Some(date("2026-10-25"))));
+    }
+
+    #[test]
+    fn relative_dates() {
+        assert_eq!(parse("+3d"), Ok(Some(date("2026-10-21"))));
+        assert_eq!(parse("-2w"), Ok(Some(date("2026-10-04"))));
+        assert_eq!(parse("+1m"), Ok(Some(date("2026-11-18"))));
+        assert_eq!(parse("-12m"), Ok(Some(date("2025-10-18"))));
+    }
+
+    #[test]
+    fn absolute_dates_without_a_year_are_not_in_the_past() {
+        assert_eq!(parse("2024-05-01"), Ok(Some(date("2024-05-01"))));
+        assert_eq!(parse("12/24"), Ok(Some(date("2026-12-24"))));
+        assert_eq!(parse("10-01"), Ok(Some(date("2027-10-01"))));
+    }
+
+    #[test]
+    fn out_of_range_and_malformed_input_is_rejected() {
+        for input in [
+            "+99999999d",
+            "-99999999999999w",
+            "+99999999999999999999d",
+            "+4294967297m",
+            "+-3d",
+            "+d",
+            "3d",
+            "02-30",
+            "someday",
+        ] {
+            assert_eq!(
+                parse(input),
+                Err(format!("Could not understand the date '{}'.", input)),
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use chrono::Duration;
+
+// 作業時間の入力を解釈する。"1h30m", "45m", "2h", "1.5h" の形式に対応し、
+// 単位が無い数値は分として扱う
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim().to_lowercase();
+    let invalid = || format!("Could not understand the duration '{}'.", input);
+    if input.is_empty() {
+        return Err(invalid());
+    }
+    match input.parse::<u32>() {
+        Ok(0) => return Err(invalid()),
+        Ok(minutes) => return Ok(Duration::minutes(minutes as i64)),
+        Err(_) => {}
+    }
+
+    let mut total_minutes = 0.0;
+    let mut number = String::new();
+    for c in input.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'h' | 'm' => {
+                let value: f64 = number.parse().map_err(|_| invalid())?;
+                total_minutes += if c == 'h' { value * 60.0 } else { value };
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    if !number.is_empty() || total_minutes <= 0.0 {
+        return Err(invalid());
+    }
+    // 大きすぎる値は Duration で表せないため解釈できない入力として扱う
+    Duration::try_minutes(total_minutes.round() as i64).ok_or_else(invalid)
+}
+
+// 作業時間を "1h30m" の形式で表す
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hours_and_minutes() {
+        assert_eq!(parse_duration("45"), Ok(Duration::minutes(45)));
+        assert_eq!(parse_duration("45m"), Ok(Duration::minutes(45)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::minutes(120)));
+        assert_eq!(parse_duration(" 1H30m "), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::minutes(90)));
+    }
+
+    #[test]
+    fn malformed_and_out_of_range_input_is_rejected() {
+        for input in [
+            "",
+            "0",
+            "0h",
+            "h",
+            "1h30",
+            "1.2.3h",
+            "-1h",
+            "1d",
+            "999999999999999999h",
+        ] {
+            assert_eq!(
+                parse_duration(input),
+                Err(format!("Could not understand the duration '{}'.", input)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn formats_hours_and_minutes() {
+        assert_eq!(format_duration(Duration::minutes(45)), "45m");
+        assert_eq!(format_duration(Duration::minutes(120)), "2h");
+        assert_eq!(format_duration(Duration::minutes(90)), "1h30m");
+    }
+}
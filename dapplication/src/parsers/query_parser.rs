@@ -0,0 +1,36 @@
+use ddomain::value_objects::field_condition::{Comparison, FieldCondition};
+
+// 絞り込みの入力の1語を条件に変換する。演算子を含まない語は None
+pub fn parse_condition(token: &str) -> Result<Option<FieldCondition>, String> {
+    let Some(position) = token.find(['=', '!', '<', '>']) else {
+        return Ok(None);
+    };
+    let (field, rest) = token.split_at(position);
+    let (operator, comparison) = Comparison::ALL
+        .iter()
+        .find(|(operator, _)| rest.starts_with(operator))
+        .ok_or_else(|| format!("Unknown operator in '{}'.", token))?;
+    if field.is_empty() {
+        return Err(format!("Missing field name in '{}'.", token));
+    }
+    Ok(Some(FieldCondition {
+        field: field.to_string(),
+        comparison: *comparison,
+        value: rest[operator.len()..].to_string(),
+    }))
+}
+
+// "component=ui severity>2" のような空白区切りの条件を解釈する(すべての条件を満たすものが対象)
+pub fn parse_query(input: &str) -> Result<Vec<FieldCondition>, String> {
+    input
+        .split_whitespace()
+        .map(|token| {
+            parse_condition(token)?.ok_or_else(|| {
+                format!(
+                    "Expected a condition like 'field=value' but got '{}'.",
+                    token
+                )
+            })
+        })
+        .collect()
+}
@@ -3,15 +3,81 @@ pub enum DomainError {
     #[error("Failed to read the file: {0}")]
     FileRead(#[from] std::io::Error),
 
+    #[error("Failed to write the file: {0}")]
+    FileWrite(std::io::Error),
+
     #[error("Failed to parse TOML data. The file may be invalid.")]
     TomlParse(#[from] toml::de::Error),
 
     #[error("Failed to serialize TOML data: {0}")]
     TomlSerialize(#[from] toml::ser::Error),
 
+    #[error("Failed to parse {0} data: {1}")]
+    DocumentParse(String, String),
+
+    #[error("Failed to serialize {0} data: {1}")]
+    DocumentSerialize(String, String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+
+    #[error("The file uses format version {0}, but this digger only understands up to version {1}. Please upgrade digger.")]
+    UnsupportedVersion(u32, u32),
+
     #[error("The file is empty or cannot be processed.")]
     EmptyFile,
 
     #[error("The file at {0} was not found.")]
     FileNotFound(String),
+
+    #[error("The sprint '{0}' does not exist.")]
+    SprintNotFound(String),
+
+    #[error("The ticket '{0}' does not exist.")]
+    TicketNotFound(String),
+
+    #[error("The ticket '{0}' exists in both {1} and {2}.")]
+    DuplicateTicket(String, String, String),
+
+    #[error("The parent of ticket '{0}' would create a cycle.")]
+    HierarchyCycle(String),
+
+    #[error("The ticket '{0}' cannot be resolved while it has open child tickets.")]
+    OpenChildren(String),
+
+    #[error("The dependencies of ticket '{0}' would create a cycle.")]
+    DependencyCycle(String),
+
+    #[error("The ticket '{0}' is blocked by {1}.")]
+    Blocked(String, String),
+
+    #[error("None of [{0}] matches a member in the file header.")]
+    UnknownMember(String),
+
+    #[error("The comment on ticket '{0}' is empty.")]
+    EmptyComment(String),
+
+    #[error("'{1}' is not a valid value for the field '{0}'.")]
+    InvalidFieldValue(String, String),
+
+    #[error("The field '{0}' is not defined in the file header.")]
+    UnknownField(String),
+
+    #[error("The status '{0}' is not declared in the file header.")]
+    UnknownStatus(String),
+
+    #[error("The ticket '{0}' cannot move from {1} to {2}.")]
+    TransitionNotAllowed(String, String, String),
+
+    #[error("Could not understand the recurrence '{0}'. Use daily, weekly:mon, monthly:15 or cron:<expression>.")]
+    InvalidRecurrence(String),
+
+    #[error("A duration of {0} minutes is too long to log.")]
+    DurationOutOfRange(i64),
+
+    #[error("'{0}' is not a source location. Use <path>:<line>.")]
+    InvalidSourceLocation(String),
+
+    #[error("The ticket '{0}' was changed by someone else. Fetch it again and retry.")]
+    StaleTicket(String),
 }
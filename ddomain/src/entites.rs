@@ -1,2 +1,11 @@
+pub mod comment;
+pub mod field_definition;
+pub mod file_header;
+pub mod member;
+pub mod sprint;
+pub mod status_definition;
 pub mod table_colors;
+pub mod tag_definition;
 pub mod ticket;
+pub mod time_entry;
+pub mod workflow;
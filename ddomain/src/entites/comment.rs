@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// チケットに追記されるコメント
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Comment {
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub body: String,
+}
@@ -0,0 +1,56 @@
+use crate::domain_errors::DomainError;
+use crate::value_objects::field_type::FieldType;
+use crate::value_objects::field_value::FieldValue;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// ファイルヘッダーで宣言するカスタムフィールド
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct FieldDefinition {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: FieldType,
+    // enum 型の選択肢
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl FieldDefinition {
+    // 入力を型に合った値に変換する
+    pub fn parse(&self, input: &str) -> Result<FieldValue, DomainError> {
+        let input = input.trim();
+        let invalid = || DomainError::InvalidFieldValue(self.name.clone(), input.to_string());
+        match self.kind {
+            FieldType::String => Ok(FieldValue::Text(input.to_string())),
+            FieldType::Number => input.parse().map(FieldValue::Number).map_err(|_| invalid()),
+            FieldType::Enum => self
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(input))
+                .map(|option| FieldValue::Text(option.clone()))
+                .ok_or_else(invalid),
+            FieldType::Date => input
+                .parse::<NaiveDate>()
+                .map(|date| FieldValue::Text(date.to_string()))
+                .map_err(|_| invalid()),
+            FieldType::Bool => match input.to_lowercase().as_str() {
+                "true" | "yes" | "y" | "1" => Ok(FieldValue::Bool(true)),
+                "false" | "no" | "n" | "0" => Ok(FieldValue::Bool(false)),
+                _ => Err(invalid()),
+            },
+        }
+    }
+
+    pub fn is_valid(&self, value: &FieldValue) -> bool {
+        match (self.kind, value) {
+            (FieldType::String, FieldValue::Text(_)) => true,
+            (FieldType::Number, FieldValue::Number(_)) => true,
+            (FieldType::Enum, FieldValue::Text(text)) => self.options.contains(text),
+            (FieldType::Date, FieldValue::Text(text)) => text.parse::<NaiveDate>().is_ok(),
+            (FieldType::Bool, FieldValue::Bool(_)) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::entites::field_definition::FieldDefinition;
+use crate::entites::member::Member;
+use crate::entites::status_definition::StatusDefinition;
+use crate::entites::tag_definition::TagDefinition;
+use crate::entites::ticket::Ticket;
+use crate::entites::workflow::Workflow;
+use crate::value_objects::estimation_scale::EstimationScale;
+use crate::value_objects::ticket_level::TicketLevel;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+// チケットファイル先頭の [header] に記述するファイル単位の設定
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct FileHeader {
+    // ファイルの書式の版。0は版を記録する前のファイル
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagDefinition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<Member>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldDefinition>,
+    // 宣言しない場合は組み込みの4つのステータスを使う
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<StatusDefinition>,
+    #[serde(default, skip_serializing_if = "EstimationScale::is_default")]
+    pub scale: EstimationScale,
+    // スケールの既定のポイントを上書きする対応表(例: XL = 13)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub estimate_points: BTreeMap<String, u32>,
+}
+
+impl FileHeader {
+    pub fn is_empty(&self) -> bool {
+        self.version == 0
+            && self.tags.is_empty()
+            && self.members.is_empty()
+            && self.fields.is_empty()
+            && self.statuses.is_empty()
+            && self.scale.is_default()
+            && self.estimate_points.is_empty()
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&TagDefinition> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    // タグ定義が無いファイルではすべてのタグを許可する
+    pub fn is_registered_tag(&self, name: &str) -> bool {
+        self.tags.is_empty() || self.tag(name).is_some()
+    }
+
+    // 定義されていないタグ(タイプミスの可能性があるもの)を (チケットID, タグ) の組で返す
+    pub fn unregistered_tags(&self, tickets: &[Ticket]) -> Vec<(String, String)> {
+        tickets
+            .iter()
+            .flat_map(|ticket| {
+                ticket
+                    .tags
+                    .iter()
+                    .filter(|tag| !self.is_registered_tag(tag))
+                    .map(|tag| (ticket.id.clone(), tag.clone()))
+            })
+            .collect()
+    }
+
+    // 候補のうち最初にメンバーと一致したものを現在のユーザーとみなす
+    pub fn find_member(&self, identities: &[String]) -> Option<&Member> {
+        identities.iter().find_map(|identity| {
+            self.members
+                .iter()
+                .find(|member| member.matches_identity(identity))
+        })
+    }
+
+    // コメントの投稿者名。名簿に無い場合は最初の候補(git の user.email など)を使う
+    pub fn author_name(&self, identities: &[String]) -> String {
+        self.find_member(identities)
+            .map(|member| member.id.clone())
+            .or_else(|| identities.first().cloned())
+            .unwrap_or_else(|| "anonymous".to_string())
+    }
+
+    pub fn is_registered_member(&self, id: &str) -> bool {
+        self.members.iter().any(|member| member.id == id)
+    }
+
+    // 名簿に存在しない担当者を (チケットID, 担当者) の組で返す
+    pub fn unregistered_assignees(&self, tickets: &[Ticket]) -> Vec<(String, String)> {
+        tickets
+            .iter()
+            .filter_map(|ticket| {
+                ticket
+                    .assignee
+                    .as_ref()
+                    .filter(|assignee| !self.is_registered_member(assignee))
+                    .map(|assignee| (ticket.id.clone(), assignee.clone()))
+            })
+            .collect()
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    // 未定義のフィールドや型に合わない値を (チケットID, フィールド名) の組で返す
+    pub fn invalid_fields(&self, tickets: &[Ticket]) -> Vec<(String, String)> {
+        tickets
+            .iter()
+            .flat_map(|ticket| {
+                ticket
+                    .fields
+                    .iter()
+                    .filter(|(name, value)| {
+                        self.field(name)
+                            .is_none_or(|definition| !definition.is_valid(value))
+                    })
+                    .map(|(name, _)| (ticket.id.clone(), name.clone()))
+            })
+            .collect()
+    }
+
+    // 複数のファイルのヘッダーを1つにまとめる。同じ名前の定義は先に読み込んだものを優先する
+    pub fn merge(&mut self, other: &FileHeader) {
+        for tag in &other.tags {
+            if self.tag(&tag.name).is_none() {
+                self.tags.push(tag.clone());
+            }
+        }
+        for member in &other.members {
+            if !self.members.iter().any(|known| known.id == member.id) {
+                self.members.push(member.clone());
+            }
+        }
+        for field in &other.fields {
+            if self.field(&field.name).is_none() {
+                self.fields.push(field.clone());
+            }
+        }
+        // どちらかが独自のステータスを宣言している場合は、組み込みのステータスも含めて並べる
+        if !self.statuses.is_empty() || !other.statuses.is_empty() {
+            let mut statuses = self.workflow().statuses;
+            for status in other.workflow().statuses {
+                if !statuses.iter().any(|known| known.name == status.name) {
+                    statuses.push(status);
+                }
+            }
+            self.statuses = statuses;
+        }
+        for (label, points) in &other.estimate_points {
+            self.estimate_points.entry(label.clone()).or_insert(*points);
+        }
+    }
+
+    pub fn workflow(&self) -> Workflow {
+        if self.statuses.is_empty() {
+            Workflow::default()
+        } else {
+            Workflow {
+                statuses: self.statuses.clone(),
+            }
+        }
+    }
+
+    // ラベルに対するポイント。上書きの対応表を優先し、スケールに無いラベルは None
+    pub fn estimate_points(&self, label: &str) -> Option<u32> {
+        self.estimate_points
+            .get(label)
+            .copied()
+            .or_else(|| self.scale.points(label))
+    }
+
+    // TUIで切り替える順の見積もり
+    pub fn estimation_levels(&self) -> Vec<TicketLevel> {
+        self.scale
+            .levels()
+            .into_iter()
+            .map(|level| {
+                let points = self.estimate_points(level.label()).unwrap_or(0);
+                TicketLevel::with_points(level.label(), points)
+            })
+            .collect()
+    }
+
+    // 取り込んだ見積もりをスケールに合わせる。ラベルが無ければ数値以上で最小の見積もり(最大を超える場合は最大)
+    pub fn level_for(&self, estimate: &str) -> TicketLevel {
+        let estimate = estimate.trim();
+        if let Some(points) = self.estimate_points(estimate) {
+            return TicketLevel::with_points(estimate, points);
+        }
+        let Some(value) = estimate.parse::<f64>().ok().filter(|value| *value > 0.0) else {
+            return TicketLevel::Unestimated;
+        };
+        if self.scale == EstimationScale::Hours {
+            let hours = value.ceil() as u32;
+            return TicketLevel::with_points(&hours.to_string(), hours);
+        }
+        let levels: Vec<TicketLevel> = self
+            .estimation_levels()
+            .into_iter()
+            .filter(|level| *level != TicketLevel::Unestimated)
+            .collect();
+        levels
+            .iter()
+            .find(|level| f64::from(level.points()) >= value)
+            .or(levels.iter().max_by_key(|level| level.points()))
+            .cloned()
+            .unwrap_or(TicketLevel::Unestimated)
+    }
+
+    // 読み込んだチケットの見積もりにスケールどおりのポイントを設定する
+    pub fn resolve_levels(&self, tickets: &mut [Ticket]) {
+        for ticket in tickets {
+            if let Some(points) = self.estimate_points(ticket.level.label()) {
+                ticket.level = TicketLevel::with_points(ticket.level.label(), points);
+            }
+        }
+    }
+
+    // スケールに含まれない見積もりを (チケットID, ラベル) の組で返す
+    pub fn invalid_levels(&self, tickets: &[Ticket]) -> Vec<(String, String)> {
+        tickets
+            .iter()
+            .filter(|ticket| self.estimate_points(ticket.level.label()).is_none())
+            .map(|ticket| (ticket.id.clone(), ticket.level.label().to_string()))
+            .collect()
+    }
+}
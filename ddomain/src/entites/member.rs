@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+// チケットの担当者として指定できるメンバー。assignee には id を記述する
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Member {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub email: String,
+}
+
+impl Member {
+    // git の user.name / user.email や $USER と一致するか
+    pub fn matches_identity(&self, identity: &str) -> bool {
+        [&self.id, &self.name, &self.email]
+            .iter()
+            .any(|value| !value.is_empty() && value.eq_ignore_ascii_case(identity))
+    }
+}
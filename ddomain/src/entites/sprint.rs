@@ -0,0 +1,122 @@
+use chrono::{Duration, NaiveDate};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Sprint {
+    pub name: String,
+    #[serde(deserialize_with = "date")]
+    pub start: NaiveDate,
+    #[serde(deserialize_with = "date")]
+    pub end: NaiveDate,
+    #[serde(default)]
+    pub goal: String,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+// ファイルを手で編集した場合の `start = 2026-10-10` のような TOML の日付も受け付ける
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DateInput {
+    Text(String),
+    Toml(toml::value::Datetime),
+}
+
+fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+    match DateInput::deserialize(deserializer)? {
+        DateInput::Text(text) => text.parse().map_err(de::Error::custom),
+        DateInput::Toml(datetime) => datetime
+            .date
+            .and_then(|date| {
+                NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+            })
+            .ok_or_else(|| de::Error::custom(format!("'{}' is not a date", datetime))),
+    }
+}
+
+impl Sprint {
+    pub fn new(name: String, start: NaiveDate, end: NaiveDate, goal: String) -> Self {
+        Sprint {
+            name,
+            start,
+            end,
+            goal,
+            closed: false,
+        }
+    }
+
+    // 指定日がスプリント期間内かつ未クローズであれば進行中とみなす
+    pub fn is_active_on(&self, date: NaiveDate) -> bool {
+        !self.closed && self.start <= date && date <= self.end
+    }
+
+    // 同じ長さで直後に続くスプリントを生成する(末尾の番号があれば繰り上げる)。
+    // 名前は sprints にあるもの(クローズ済みを含む)と重ならないようにする
+    pub fn following(&self, sprints: &[Sprint]) -> Sprint {
+        let start = self.end + Duration::days(1);
+        let end = start + (self.end - self.start);
+        let taken = |name: &String| sprints.iter().any(|sprint| sprint.name == *name);
+        let name = next_sprint_names(&self.name)
+            .find(|name| !taken(name))
+            .unwrap_or_default();
+        Sprint::new(name, start, end, String::new())
+    }
+}
+
+// 次のスプリントの名前の候補。"Sprint 9" なら "Sprint 10", "Sprint 11", ... の順。
+// 番号が無い場合や番号を繰り上げられない場合は "Sprint (next)", "Sprint (next 2)", ...
+fn next_sprint_names(name: &str) -> impl Iterator<Item = String> + '_ {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let numbered = name[prefix.len()..]
+        .parse::<u64>()
+        .ok()
+        .into_iter()
+        .flat_map(|number| (1..).map_while(move |step| number.checked_add(step)))
+        .map(move |number| format!("{}{}", prefix, number));
+    let suffixed = std::iter::once(format!("{} (next)", name))
+        .chain((2_u64..).map(move |count| format!("{} (next {})", name, count)));
+    numbered.chain(suffixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprint(name: &str) -> Sprint {
+        let start = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+        Sprint::new(
+            name.to_string(),
+            start,
+            start + Duration::days(13),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn the_following_sprint_starts_the_next_day_with_the_same_length() {
+        let next = sprint("Sprint 9").following(&[]);
+        assert_eq!(next.name, "Sprint 10");
+        assert_eq!(next.start.to_string(), "2026-10-19");
+        assert_eq!(next.end.to_string(), "2026-11-01");
+    }
+
+    #[test]
+    fn names_of_existing_sprints_are_skipped() {
+        let sprints = [sprint("Sprint 1"), sprint("Sprint 2"), sprint("Sprint 3")];
+        assert_eq!(sprints[0].following(&sprints).name, "Sprint 4");
+        let unnumbered = [sprint("Kickoff"), sprint("Kickoff (next)")];
+        assert_eq!(
+            unnumbered[0].following(&unnumbered).name,
+            "Kickoff (next 2)"
+        );
+    }
+
+    #[test]
+    fn the_largest_number_does_not_overflow() {
+        let last = sprint(&format!("Sprint {}", u64::MAX));
+        assert_eq!(
+            last.following(&[]).name,
+            format!("Sprint {} (next)", u64::MAX)
+        );
+    }
+}
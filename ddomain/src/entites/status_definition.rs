@@ -0,0 +1,19 @@
+use crate::value_objects::status_category::StatusCategory;
+use crate::value_objects::ticket_status::TicketStatus;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct StatusDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub category: StatusCategory,
+    // 移行できるステータス名。空の場合はどのステータスにも移行できる
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<String>,
+}
+
+impl StatusDefinition {
+    pub fn status(&self) -> TicketStatus {
+        TicketStatus::with_category(&self.name, self.category)
+    }
+}
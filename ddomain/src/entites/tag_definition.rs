@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TagDefinition {
+    pub name: String,
+    // "red" や "#ff8800" のような色指定
+    #[serde(default)]
+    pub color: String,
+    #[serde(default)]
+    pub description: String,
+}
@@ -1,35 +1,252 @@
+use crate::domain_errors::DomainError;
+use crate::entites::comment::Comment;
+use crate::entites::field_definition::FieldDefinition;
+use crate::entites::time_entry::TimeEntry;
+use crate::value_objects::due_bucket::DueBucket;
+use crate::value_objects::field_value::FieldValue;
+use crate::value_objects::priority::Priority;
+use crate::value_objects::recurrence::Recurrence;
+use crate::value_objects::source_location::SourceLocation;
+use crate::value_objects::status_category::StatusCategory;
 use crate::value_objects::ticket_level::TicketLevel;
 use crate::value_objects::ticket_status::TicketStatus;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Ticket {
     pub id: String,
+    // 取り込み元での ID(GH-12, PROJ-3 など)。同じチケットを二重に取り込まないために使う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
     pub level: TicketLevel,
     pub title: String,
     pub status: TicketStatus,
+    #[serde(default, skip_serializing_if = "Priority::is_default")]
+    pub priority: Priority,
+    // 同じ優先度の中での手動の順位(小さいほど上)。0は未設定
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub rank: u32,
     pub created_at: DateTime<Utc>,
     pub resolved_at: Option<DateTime<Utc>>,
+    // 完了から未完了に最後に戻した日時
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reopened_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    // 完了すると次のチケットを作る繰り返しの規則
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_entries: Vec<TimeEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+    // ファイルヘッダーで宣言したカスタムフィールドの値
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldValue>,
+    // ソースコードの TODO などのコメントから作ったチケットの、コメントの位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceLocation>,
+    // 複数のファイルをまとめて開いたときの所属プロジェクト(ファイル名)。ファイルには保存しない
+    #[serde(skip)]
+    pub project: Option<String>,
 }
 
 impl Ticket {
     pub fn new(id: String, level: TicketLevel, title: String, status: TicketStatus) -> Self {
         Ticket {
             id,
+            external_id: None,
             level,
             title,
             status,
+            priority: Priority::default(),
+            rank: 0,
             created_at: Utc::now(),
             resolved_at: None,
+            reopened_at: None,
+            sprint: None,
+            parent: None,
+            blocked_by: Vec::new(),
+            tags: Vec::new(),
+            assignee: None,
+            due: None,
+            recurrence: None,
+            time_entries: Vec::new(),
+            comments: Vec::new(),
+            fields: BTreeMap::new(),
+            source: None,
+            project: None,
         }
     }
 
     pub fn set_status(&mut self, new_status: TicketStatus) {
-        if new_status == TicketStatus::Resolved {
+        if new_status.category() == StatusCategory::Done {
             // 解決日時を現在のUTC時間に設定
             self.resolved_at = Some(Utc::now());
+        } else if self.status.category() == StatusCategory::Done {
+            self.reopened_at = Some(Utc::now());
         }
         self.status = new_status;
     }
+
+    // 空のタグと重複を取り除いて設定する
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags.clear();
+        for tag in tags {
+            if !tag.is_empty() && !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+    }
+
+    // 未着手または作業中のチケット
+    pub fn is_open(&self) -> bool {
+        self.status.category().is_open()
+    }
+
+    // 現在の未完了の期間が始まった日時(最後に戻した日時、無ければ作成日時)
+    pub fn open_since(&self) -> DateTime<Utc> {
+        self.reopened_at.map_or(self.created_at, |reopened_at| {
+            reopened_at.max(self.created_at)
+        })
+    }
+
+    // 指定時刻の時点で解決済みだったか(解決日時が無い解決済みチケットは作成時から解決済みとみなす)
+    pub fn is_resolved_at(&self, at: DateTime<Utc>) -> bool {
+        self.status.category() == StatusCategory::Done && self.resolved_at.is_none_or(|r| r <= at)
+    }
+
+    // 未完了で期日があるチケットのみ区分を持つ
+    pub fn due_bucket(&self, today: NaiveDate) -> Option<DueBucket> {
+        self.due
+            .filter(|_| self.is_open())
+            .map(|due| DueBucket::classify(due, today))
+    }
+
+    pub fn is_timer_running(&self) -> bool {
+        self.time_entries.iter().any(TimeEntry::is_running)
+    }
+
+    // 計測中のタイマーが無い場合のみ開始する
+    pub fn start_timer(&mut self, member: Option<String>, now: DateTime<Utc>) {
+        if !self.is_timer_running() {
+            self.time_entries.push(TimeEntry {
+                start: now,
+                end: None,
+                member,
+                note: String::new(),
+            });
+        }
+    }
+
+    pub fn stop_timer(&mut self, now: DateTime<Utc>) {
+        for entry in self
+            .time_entries
+            .iter_mut()
+            .filter(|entry| entry.is_running())
+        {
+            entry.end = Some(now);
+        }
+    }
+
+    // 手入力の作業時間は現在時刻で終了したものとして記録する
+    pub fn log_time(
+        &mut self,
+        duration: Duration,
+        member: Option<String>,
+        note: String,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        let start = now
+            .checked_sub_signed(duration)
+            .ok_or(DomainError::DurationOutOfRange(duration.num_minutes()))?;
+        self.time_entries.push(TimeEntry {
+            start,
+            end: Some(now),
+            member,
+            note,
+        });
+        Ok(())
+    }
+
+    pub fn tracked_time(&self, now: DateTime<Utc>) -> Duration {
+        self.time_entries
+            .iter()
+            .map(|entry| entry.duration(now))
+            .sum()
+    }
+
+    // 空白のみのコメントは追加しない
+    pub fn add_comment(
+        &mut self,
+        author: String,
+        body: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(DomainError::EmptyComment(self.id.clone()));
+        }
+        self.comments.push(Comment {
+            author,
+            created_at: now,
+            body: body.to_string(),
+        });
+        Ok(())
+    }
+
+    // 空の入力は値を取り除く
+    pub fn set_field(
+        &mut self,
+        definition: &FieldDefinition,
+        input: &str,
+    ) -> Result<(), DomainError> {
+        if input.trim().is_empty() {
+            self.fields.remove(&definition.name);
+        } else {
+            let value = definition.parse(input)?;
+            self.fields.insert(definition.name.clone(), value);
+        }
+        Ok(())
+    }
+
+    // 絞り込み条件で参照する値。組み込みの項目を優先し、無ければカスタムフィールドを探す
+    pub fn field_texts(&self, name: &str) -> Vec<String> {
+        match name {
+            "id" => vec![self.id.clone()],
+            "title" => vec![self.title.clone()],
+            "status" => vec![self.status.clone().into()],
+            "category" => vec![self.status.category().into()],
+            "level" => vec![self.level.clone().into()],
+            "priority" => vec![self.priority.into()],
+            "points" => vec![self.level.points().to_string()],
+            "tag" => self.tags.clone(),
+            "assignee" => self.assignee.iter().cloned().collect(),
+            "sprint" => self.sprint.iter().cloned().collect(),
+            "parent" => self.parent.iter().cloned().collect(),
+            "due" => self.due.iter().map(|due| due.to_string()).collect(),
+            _ => self
+                .fields
+                .get(name)
+                .map(|value| value.to_string())
+                .into_iter()
+                .collect(),
+        }
+    }
 }
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+// チケットに記録する作業時間。end が無いものは計測中のタイマー
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct TimeEntry {
+    pub start: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl TimeEntry {
+    pub fn is_running(&self) -> bool {
+        self.end.is_none()
+    }
+
+    // 計測中の場合は現在時刻までの時間
+    pub fn duration(&self, now: DateTime<Utc>) -> Duration {
+        self.end.unwrap_or(now) - self.start
+    }
+}
@@ -0,0 +1,111 @@
+use crate::entites::status_definition::StatusDefinition;
+use crate::entites::ticket::Ticket;
+use crate::value_objects::status_category::StatusCategory;
+use crate::value_objects::ticket_status::TicketStatus;
+
+// ファイルで使えるステータスの一覧(宣言順)と移行の規則
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    pub statuses: Vec<StatusDefinition>,
+}
+
+impl Default for Workflow {
+    // 組み込みの4つのステータスを自由に移行できる
+    fn default() -> Self {
+        Workflow {
+            statuses: [
+                TicketStatus::Pending,
+                TicketStatus::Wip,
+                TicketStatus::Resolved,
+                TicketStatus::Canceled,
+            ]
+            .into_iter()
+            .map(|status| StatusDefinition {
+                name: status.name().to_string(),
+                category: status.category(),
+                transitions: Vec::new(),
+            })
+            .collect(),
+        }
+    }
+}
+
+impl Workflow {
+    pub fn find(&self, name: &str) -> Option<&StatusDefinition> {
+        self.statuses.iter().find(|status| status.name == name)
+    }
+
+    pub fn is_declared(&self, status: &TicketStatus) -> bool {
+        self.find(status.name()).is_some()
+    }
+
+    // 新規のチケットに使う最初の未着手のステータス
+    pub fn initial_status(&self) -> TicketStatus {
+        self.statuses
+            .iter()
+            .find(|status| status.category == StatusCategory::Todo)
+            .or(self.statuses.first())
+            .map_or(TicketStatus::Pending, StatusDefinition::status)
+    }
+
+    // 現在のステータスから移行できる最初の完了のステータス
+    pub fn resolved_status(&self, current: &TicketStatus) -> Option<TicketStatus> {
+        self.statuses
+            .iter()
+            .filter(|status| status.category == StatusCategory::Done)
+            .map(StatusDefinition::status)
+            .find(|status| self.allows(current, status))
+    }
+
+    // 取り込んだチケットのステータス。同じ名前(大文字小文字は区別しない)が無ければ同じ分類の最初のステータス
+    pub fn status_for(&self, name: &str, category: StatusCategory) -> TicketStatus {
+        self.statuses
+            .iter()
+            .find(|status| status.name.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                self.statuses
+                    .iter()
+                    .find(|status| status.category == category)
+            })
+            .map_or_else(|| self.initial_status(), StatusDefinition::status)
+    }
+
+    // 読み込んだチケットのステータスに宣言どおりの分類を設定する
+    pub fn classify(&self, tickets: &mut [Ticket]) {
+        for ticket in tickets {
+            if let Some(definition) = self.find(ticket.status.name()) {
+                ticket.status = definition.status();
+            }
+        }
+    }
+
+    pub fn allows(&self, from: &TicketStatus, to: &TicketStatus) -> bool {
+        self.is_declared(to)
+            && self.find(from.name()).is_none_or(|definition| {
+                definition.transitions.is_empty()
+                    || definition.transitions.iter().any(|name| name == to.name())
+            })
+    }
+
+    // 宣言順で現在のステータスの次にある、移行できるステータス
+    pub fn next_status(&self, current: &TicketStatus) -> Option<TicketStatus> {
+        // 宣言されていないステータスからは先頭から探す
+        let position = self
+            .statuses
+            .iter()
+            .position(|status| status.name == current.name())
+            .unwrap_or(self.statuses.len().saturating_sub(1));
+        (1..=self.statuses.len())
+            .map(|offset| self.statuses[(position + offset) % self.statuses.len()].status())
+            .find(|status| status != current && self.allows(current, status))
+    }
+
+    // 宣言されていないステータスを (チケットID, ステータス) の組で返す
+    pub fn undeclared_statuses(&self, tickets: &[Ticket]) -> Vec<(String, String)> {
+        tickets
+            .iter()
+            .filter(|ticket| !self.is_declared(&ticket.status))
+            .map(|ticket| (ticket.id.clone(), ticket.status.name().to_string()))
+            .collect()
+    }
+}
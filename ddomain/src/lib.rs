@@ -1,4 +1,5 @@
 pub mod domain_errors;
 pub mod entites;
 pub mod repositories;
+pub mod services;
 pub mod value_objects;
@@ -1,8 +1,18 @@
 use crate::domain_errors::DomainError;
+use crate::entites::file_header::FileHeader;
+use crate::entites::sprint::Sprint;
 use crate::entites::ticket::Ticket;
 use color_eyre::Result;
 
 pub trait TicketRepository {
     fn fetch_tickets(&self) -> Result<Vec<Ticket>, DomainError>;
+    fn save_tickets(&self, tickets: &[Ticket]) -> Result<(), DomainError>;
+    fn fetch_sprints(&self) -> Result<Vec<Sprint>, DomainError>;
+    fn save_sprints(&self, sprints: &[Sprint]) -> Result<(), DomainError>;
+    fn fetch_header(&self) -> Result<FileHeader, DomainError>;
     fn ensure_file_exists_with_template(&self) -> Result<()>;
+    // チケットを移動できるプロジェクトの一覧。1つのファイルだけを扱う場合は空
+    fn projects(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
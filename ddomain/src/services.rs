@@ -0,0 +1,11 @@
+pub mod code_markers;
+pub mod commit_references;
+pub mod sprint_planning;
+pub mod status_transition;
+pub mod ticket_dependencies;
+pub mod ticket_hierarchy;
+pub mod ticket_ids;
+pub mod ticket_ordering;
+pub mod ticket_recurrence;
+pub mod ticket_statistics;
+pub mod time_tracking;
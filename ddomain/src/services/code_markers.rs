@@ -0,0 +1,61 @@
+// ソースコードのコメントに残された作業の印
+const MARKER_KINDS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+// 印の直前に置かれるコメントの始まり
+const COMMENT_STARTS: [&str; 7] = ["//", "#", "--", "/*", "*", ";", "<!--"];
+
+// コメントの終わりとして取り除く記号
+const COMMENT_ENDS: [&str; 2] = ["*/", "-->"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeMarker {
+    // TODO, FIXME, HACK のいずれか
+    pub kind: String,
+    pub text: String,
+}
+
+impl CodeMarker {
+    // チケットのタイトル。本文が無い場合は印の種類
+    pub fn title(&self) -> String {
+        if self.text.is_empty() {
+            self.kind.clone()
+        } else {
+            self.text.clone()
+        }
+    }
+
+    // チケットに付けるタグ(todo, fixme, hack)
+    pub fn tag(&self) -> String {
+        self.kind.to_lowercase()
+    }
+}
+
+// 行のコメントにある最初の印。"// TODO: ...", "# FIXME(yu) ...", "/* HACK */" などを認識する。
+// 識別子の一部(TODOS など)や、コメントの始まりの直後に無いもの(文字列の中など)は無視する
+pub fn find_marker(line: &str) -> Option<CodeMarker> {
+    MARKER_KINDS
+        .iter()
+        .flat_map(|kind| line.match_indices(kind))
+        .filter(|(index, kind)| {
+            let before = line[..*index].trim_end();
+            let after = line[index + kind.len()..].chars().next();
+            COMMENT_STARTS.iter().any(|start| before.ends_with(start))
+                && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+        .min_by_key(|(index, _)| *index)
+        .map(|(index, kind)| {
+            let mut text = line[index + kind.len()..].trim();
+            // "TODO(yu):" のような担当者の注記は本文に含めない
+            if let Some(rest) = text.strip_prefix('(') {
+                text = rest.split_once(')').map_or(rest, |(_, rest)| rest);
+            }
+            let mut text = text.trim_start_matches([':', '-', ' ']).trim_end();
+            for end in COMMENT_ENDS {
+                text = text.strip_suffix(end).unwrap_or(text).trim_end();
+            }
+            CodeMarker {
+                kind: kind.to_string(),
+                text: text.to_string(),
+            }
+        })
+}
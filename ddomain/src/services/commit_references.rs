@@ -0,0 +1,164 @@
+use crate::entites::ticket::Ticket;
+use chrono::{DateTime, Utc};
+
+// 直後のチケットを完了させるコミットメッセージの語
+const CLOSING_KEYWORDS: [&str; 9] = [
+    "fix", "fixes", "fixed", "close", "closes", "closed", "resolve", "resolves", "resolved",
+];
+
+// コミットメッセージ中のチケットへの言及
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicketReference {
+    pub id: String,
+    // "fixes DIG-12" のように完了を示す語が前にあるか
+    pub closes: bool,
+}
+
+// メッセージに含まれる既存のチケットのID(大文字小文字は区別しない)を出現順に返す。
+// "fixes DIG-1, DIG-2 and DIG-3" のように完了を示す語に続くIDはすべて closes になる
+pub fn references(message: &str, tickets: &[Ticket]) -> Vec<TicketReference> {
+    let mut found: Vec<TicketReference> = Vec::new();
+    let mut closing = false;
+    for word in message.split_whitespace() {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '_');
+        let lower = word.to_lowercase();
+        if CLOSING_KEYWORDS.contains(&lower.as_str()) {
+            closing = true;
+            continue;
+        }
+        let Some(ticket) = tickets
+            .iter()
+            .find(|ticket| ticket.id.eq_ignore_ascii_case(word))
+        else {
+            closing = closing && lower == "and";
+            continue;
+        };
+        match found.iter_mut().find(|reference| reference.id == ticket.id) {
+            Some(reference) => reference.closes |= closing,
+            None => found.push(TicketReference {
+                id: ticket.id.clone(),
+                closes: closing,
+            }),
+        }
+    }
+    found
+}
+
+// コミットがチケットを完了させるか。完了を示す言及でも、チケットが作られる前や
+// 最後に未完了へ戻される前のコミットは数えない
+pub fn closes(
+    ticket: &Ticket,
+    committed_at: DateTime<Utc>,
+    references: &[TicketReference],
+) -> bool {
+    committed_at > ticket.open_since()
+        && references
+            .iter()
+            .any(|reference| reference.id == ticket.id && reference.closes)
+}
+
+// チケットから作るブランチ名("DIG-12" と "Fix login bug" なら "dig-12-fix-login-bug")。
+// タイトルの英数字以外は区切りとして扱い、長すぎる場合は単語の途中で切らずに詰める
+pub fn branch_name(ticket: &Ticket) -> String {
+    const MAX_LENGTH: usize = 50;
+    let mut name = ticket.id.to_lowercase();
+    let words = ticket
+        .title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase);
+    for word in words {
+        if name.len() + 1 + word.len() > MAX_LENGTH {
+            break;
+        }
+        name.push('-');
+        name.push_str(&word);
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ticket_level::TicketLevel;
+    use crate::value_objects::ticket_status::TicketStatus;
+
+    fn ticket(id: &str) -> Ticket {
+        let mut ticket = Ticket::new(
+            id.to_string(),
+            TicketLevel::default(),
+            "Fix login bug".to_string(),
+            TicketStatus::default(),
+        );
+        ticket.created_at = at("2026-10-01T09:00:00Z");
+        ticket
+    }
+
+    fn at(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    fn reference(id: &str, closes: bool) -> TicketReference {
+        TicketReference {
+            id: id.to_string(),
+            closes,
+        }
+    }
+
+    #[test]
+    fn closing_keywords_apply_to_every_listed_id() {
+        let tickets = [ticket("DIG-1"), ticket("DIG-2"), ticket("DIG-3")];
+        assert_eq!(
+            references("Fixes dig-1, DIG-2 and DIG-3.", &tickets),
+            [
+                reference("DIG-1", true),
+                reference("DIG-2", true),
+                reference("DIG-3", true)
+            ]
+        );
+    }
+
+    #[test]
+    fn ids_after_other_words_are_only_mentioned() {
+        let tickets = [ticket("DIG-1"), ticket("DIG-2")];
+        assert_eq!(
+            references("Fix DIG-1 as noted in DIG-2", &tickets),
+            [reference("DIG-1", true), reference("DIG-2", false)]
+        );
+    }
+
+    #[test]
+    fn unknown_ids_are_ignored_and_repeated_ids_are_merged() {
+        let tickets = [ticket("DIG-1")];
+        assert_eq!(
+            references("See DIG-1 and DIG-9, closes DIG-1", &tickets),
+            [reference("DIG-1", true)]
+        );
+    }
+
+    #[test]
+    fn only_commits_after_the_last_reopen_close_a_ticket() {
+        let mut ticket = ticket("DIG-1");
+        let fixes = [reference("DIG-1", true)];
+        assert!(closes(&ticket, at("2026-10-02T09:00:00Z"), &fixes));
+        assert!(!closes(&ticket, at("2026-09-30T09:00:00Z"), &fixes));
+        assert!(!closes(
+            &ticket,
+            at("2026-10-02T09:00:00Z"),
+            &[reference("DIG-1", false)]
+        ));
+
+        ticket.reopened_at = Some(at("2026-10-03T09:00:00Z"));
+        assert!(!closes(&ticket, at("2026-10-02T09:00:00Z"), &fixes));
+        assert!(closes(&ticket, at("2026-10-04T09:00:00Z"), &fixes));
+    }
+
+    #[test]
+    fn branch_names_stop_between_words() {
+        let mut ticket = ticket("DIG-12");
+        assert_eq!(branch_name(&ticket), "dig-12-fix-login-bug");
+        ticket.title = "word ".repeat(20);
+        let name = branch_name(&ticket);
+        assert!(name.len() <= 50 && name.ends_with("-word"));
+    }
+}
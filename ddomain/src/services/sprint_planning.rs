@@ -0,0 +1,111 @@
+use crate::domain_errors::DomainError;
+use crate::entites::sprint::Sprint;
+use crate::entites::ticket::Ticket;
+use chrono::NaiveDate;
+
+pub fn current_sprint(sprints: &[Sprint], today: NaiveDate) -> Option<&Sprint> {
+    sprints.iter().find(|sprint| sprint.is_active_on(today))
+}
+
+// クローズする対象のスプリント。開始済みで未クローズのうち最も新しいもの。
+// 終了日を過ぎてもクローズするまでは未完了のチケットを繰り越せるようにする
+pub fn sprint_to_close(sprints: &[Sprint], today: NaiveDate) -> Option<&Sprint> {
+    sprints
+        .iter()
+        .filter(|sprint| !sprint.closed && sprint.start <= today)
+        .max_by_key(|sprint| sprint.start)
+}
+
+// スプリントをクローズし、未完了のチケットを次のスプリントへ繰り越す。
+// 次のスプリントが定義されていない場合は同じ長さのスプリントを追加する。
+// 戻り値は繰り越し先のスプリント名
+pub fn close_sprint(
+    sprints: &mut Vec<Sprint>,
+    tickets: &mut [Ticket],
+    name: &str,
+) -> Result<String, DomainError> {
+    let index = sprints
+        .iter()
+        .position(|sprint| sprint.name == name)
+        .ok_or_else(|| DomainError::SprintNotFound(name.to_string()))?;
+    sprints[index].closed = true;
+    let closing = sprints[index].clone();
+
+    let next_name = match sprints
+        .iter()
+        .filter(|sprint| !sprint.closed && sprint.start > closing.start)
+        .min_by_key(|sprint| sprint.start)
+    {
+        Some(next) => next.name.clone(),
+        None => {
+            let next = closing.following(sprints);
+            let next_name = next.name.clone();
+            sprints.push(next);
+            next_name
+        }
+    };
+
+    for ticket in tickets
+        .iter_mut()
+        .filter(|ticket| ticket.sprint.as_deref() == Some(name) && ticket.is_open())
+    {
+        ticket.sprint = Some(next_name.clone());
+    }
+
+    Ok(next_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ticket_level::TicketLevel;
+    use crate::value_objects::ticket_status::TicketStatus;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn sprint(name: &str, start: &str, end: &str) -> Sprint {
+        Sprint::new(name.to_string(), date(start), date(end), String::new())
+    }
+
+    #[test]
+    fn a_sprint_past_its_end_can_still_be_closed() {
+        let mut sprints = vec![
+            sprint("Sprint 1", "2026-09-21", "2026-10-04"),
+            sprint("Sprint 2", "2026-10-05", "2026-10-16"),
+            sprint("Sprint 3", "2026-10-19", "2026-10-30"),
+        ];
+        sprints[0].closed = true;
+        let today = date("2026-10-18");
+        assert!(current_sprint(&sprints, today).is_none());
+        assert_eq!(sprint_to_close(&sprints, today).unwrap().name, "Sprint 2");
+        assert!(sprint_to_close(&sprints[..1], today).is_none());
+    }
+
+    #[test]
+    fn open_tickets_move_to_the_next_sprint() {
+        let mut sprints = vec![sprint("Sprint 1", "2026-10-05", "2026-10-16")];
+        let mut tickets: Vec<Ticket> = [TicketStatus::Pending, TicketStatus::Resolved]
+            .into_iter()
+            .enumerate()
+            .map(|(index, status)| {
+                let mut ticket = Ticket::new(
+                    format!("DIG-{}", index + 1),
+                    TicketLevel::Unestimated,
+                    String::new(),
+                    status,
+                );
+                ticket.sprint = Some("Sprint 1".to_string());
+                ticket
+            })
+            .collect();
+
+        let next = close_sprint(&mut sprints, &mut tickets, "Sprint 1").unwrap();
+        assert_eq!(next, "Sprint 2");
+        assert!(sprints[0].closed);
+        assert_eq!(sprints[1].start, date("2026-10-17"));
+        assert_eq!(tickets[0].sprint.as_deref(), Some("Sprint 2"));
+        assert_eq!(tickets[1].sprint.as_deref(), Some("Sprint 1"));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::domain_errors::DomainError;
+use crate::entites::ticket::Ticket;
+use crate::entites::workflow::Workflow;
+use crate::services::{ticket_dependencies, ticket_hierarchy, ticket_recurrence};
+use crate::value_objects::status_category::StatusCategory;
+use crate::value_objects::ticket_status::TicketStatus;
+use chrono::Utc;
+
+// チケットのステータスを変更する。
+// ワークフローで許可されていない移行はできない。
+// 子チケットが未完了の親は完了にできず、ブロックされたチケットは強制しない限り作業中にできない。
+// 作業中に移行するとタイマーを開始し、作業中から外れると計測中のタイマーを止める。
+// 繰り返しのチケットが完了すると次のチケットを作り、そのIDを返す
+pub fn change_status(
+    tickets: &mut Vec<Ticket>,
+    workflow: &Workflow,
+    id: &str,
+    new_status: TicketStatus,
+    force: bool,
+    actor: Option<&str>,
+) -> Result<Option<String>, DomainError> {
+    let current = tickets
+        .iter()
+        .find(|ticket| ticket.id == id)
+        .map(|ticket| ticket.status.clone())
+        .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))?;
+    if !workflow.is_declared(&new_status) {
+        return Err(DomainError::UnknownStatus(new_status.name().to_string()));
+    }
+    if !workflow.allows(&current, &new_status) {
+        return Err(DomainError::TransitionNotAllowed(
+            id.to_string(),
+            current.into(),
+            new_status.into(),
+        ));
+    }
+    let category = new_status.category();
+    if category == StatusCategory::Done && ticket_hierarchy::has_open_children(tickets, id) {
+        return Err(DomainError::OpenChildren(id.to_string()));
+    }
+    if category == StatusCategory::InProgress && !force {
+        let blockers = ticket_dependencies::open_blockers(tickets, id);
+        if !blockers.is_empty() {
+            let blocker_ids: Vec<&str> = blockers.iter().map(|ticket| ticket.id.as_str()).collect();
+            return Err(DomainError::Blocked(id.to_string(), blocker_ids.join(", ")));
+        }
+    }
+    let ticket = tickets
+        .iter_mut()
+        .find(|ticket| ticket.id == id)
+        .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))?;
+    let now = Utc::now();
+    if category == StatusCategory::InProgress {
+        ticket.start_timer(actor.map(str::to_string), now);
+    } else {
+        ticket.stop_timer(now);
+    }
+    ticket.set_status(new_status);
+    if category == StatusCategory::Done {
+        return Ok(ticket_recurrence::spawn_next(
+            tickets,
+            id,
+            workflow,
+            now.date_naive(),
+        ));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ticket_level::TicketLevel;
+
+    fn ticket(id: &str, status: TicketStatus) -> Ticket {
+        Ticket::new(
+            id.to_string(),
+            TicketLevel::Unestimated,
+            id.to_string(),
+            status,
+        )
+    }
+
+    fn parent_with_child(child_status: TicketStatus) -> Vec<Ticket> {
+        let mut child = ticket("DIG-2", child_status);
+        child.parent = Some("DIG-1".to_string());
+        vec![ticket("DIG-1", TicketStatus::Wip), child]
+    }
+
+    #[test]
+    fn a_parent_with_open_children_cannot_be_resolved() {
+        let mut tickets = parent_with_child(TicketStatus::Wip);
+        let workflow = Workflow::default();
+        assert!(matches!(
+            change_status(&mut tickets, &workflow, "DIG-1", TicketStatus::Resolved, true, None),
+            Err(DomainError::OpenChildren(id)) if id == "DIG-1"
+        ));
+        assert_eq!(tickets[0].status, TicketStatus::Wip);
+
+        // 完了以外への移行は妨げない
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-1",
+            TicketStatus::Pending,
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(tickets[0].status, TicketStatus::Pending);
+    }
+
+    #[test]
+    fn a_parent_is_resolved_once_its_children_are_done() {
+        let mut tickets = parent_with_child(TicketStatus::Canceled);
+        let workflow = Workflow::default();
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-1",
+            TicketStatus::Resolved,
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(tickets[0].status, TicketStatus::Resolved);
+        assert!(tickets[0].resolved_at.is_some());
+    }
+
+    fn blocked() -> Vec<Ticket> {
+        let mut blocked = ticket("DIG-2", TicketStatus::Pending);
+        blocked.blocked_by = vec!["DIG-1".to_string()];
+        vec![ticket("DIG-1", TicketStatus::Pending), blocked]
+    }
+
+    #[test]
+    fn a_blocked_ticket_is_started_only_when_forced() {
+        let mut tickets = blocked();
+        let workflow = Workflow::default();
+        assert!(matches!(
+            change_status(&mut tickets, &workflow, "DIG-2", TicketStatus::Wip, false, None),
+            Err(DomainError::Blocked(id, blockers)) if id == "DIG-2" && blockers == "DIG-1"
+        ));
+        assert_eq!(tickets[1].status, TicketStatus::Pending);
+        assert!(!tickets[1].is_timer_running());
+
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-2",
+            TicketStatus::Wip,
+            true,
+            Some("alice"),
+        )
+        .unwrap();
+        assert_eq!(tickets[1].status, TicketStatus::Wip);
+        assert!(tickets[1].is_timer_running());
+    }
+
+    #[test]
+    fn a_ticket_is_no_longer_blocked_once_its_blockers_are_done() {
+        let mut tickets = blocked();
+        let workflow = Workflow::default();
+        // ブロックされていても作業中以外への移行はできる
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-2",
+            TicketStatus::Canceled,
+            false,
+            None,
+        )
+        .unwrap();
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-2",
+            TicketStatus::Pending,
+            false,
+            None,
+        )
+        .unwrap();
+
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-1",
+            TicketStatus::Resolved,
+            false,
+            None,
+        )
+        .unwrap();
+        change_status(
+            &mut tickets,
+            &workflow,
+            "DIG-2",
+            TicketStatus::Wip,
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(tickets[1].status, TicketStatus::Wip);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::domain_errors::DomainError;
+use crate::entites::ticket::Ticket;
+use std::collections::HashSet;
+
+fn find<'a>(tickets: &'a [Ticket], id: &str) -> Option<&'a Ticket> {
+    tickets.iter().find(|ticket| ticket.id == id)
+}
+
+// 指定したチケットがブロックしているチケット
+pub fn blocks<'a>(tickets: &'a [Ticket], id: &'a str) -> impl Iterator<Item = &'a Ticket> {
+    tickets
+        .iter()
+        .filter(move |ticket| ticket.blocked_by.iter().any(|blocker| blocker == id))
+}
+
+// 未完了のブロッカー
+pub fn open_blockers<'a>(tickets: &'a [Ticket], id: &str) -> Vec<&'a Ticket> {
+    find(tickets, id)
+        .map(|ticket| {
+            ticket
+                .blocked_by
+                .iter()
+                .filter_map(|blocker| find(tickets, blocker))
+                .filter(|blocker| blocker.is_open())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// blocked_by をたどって from から to に到達できるか
+fn reaches(tickets: &[Ticket], from: &str, to: &str) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![from];
+    while let Some(id) = stack.pop() {
+        if id == to {
+            return true;
+        }
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(ticket) = find(tickets, id) {
+            stack.extend(ticket.blocked_by.iter().map(String::as_str));
+        }
+    }
+    false
+}
+
+// 全チケットの依存関係を検証する(存在しないID・循環参照を検出)
+pub fn validate_dependencies(tickets: &[Ticket]) -> Result<(), DomainError> {
+    for ticket in tickets {
+        for blocker in &ticket.blocked_by {
+            if find(tickets, blocker).is_none() {
+                return Err(DomainError::TicketNotFound(blocker.clone()));
+            }
+            if reaches(tickets, blocker, &ticket.id) {
+                return Err(DomainError::DependencyCycle(ticket.id.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
+// blocker_id が id をブロックする関係を追加する
+pub fn add_dependency(
+    tickets: &mut [Ticket],
+    id: &str,
+    blocker_id: &str,
+) -> Result<(), DomainError> {
+    if find(tickets, blocker_id).is_none() {
+        return Err(DomainError::TicketNotFound(blocker_id.to_string()));
+    }
+    if reaches(tickets, blocker_id, id) {
+        return Err(DomainError::DependencyCycle(id.to_string()));
+    }
+    let ticket = tickets
+        .iter_mut()
+        .find(|ticket| ticket.id == id)
+        .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))?;
+    if !ticket
+        .blocked_by
+        .iter()
+        .any(|blocker| blocker == blocker_id)
+    {
+        ticket.blocked_by.push(blocker_id.to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ticket_level::TicketLevel;
+    use crate::value_objects::ticket_status::TicketStatus;
+
+    fn ticket(id: &str, blocked_by: &[&str]) -> Ticket {
+        let mut ticket = Ticket::new(
+            id.to_string(),
+            TicketLevel::Unestimated,
+            id.to_string(),
+            TicketStatus::Pending,
+        );
+        ticket.blocked_by = blocked_by.iter().map(|id| id.to_string()).collect();
+        ticket
+    }
+
+    // DIG-3 は DIG-2 に、DIG-2 は DIG-1 にブロックされている
+    fn chain() -> Vec<Ticket> {
+        vec![
+            ticket("DIG-1", &[]),
+            ticket("DIG-2", &["DIG-1"]),
+            ticket("DIG-3", &["DIG-2"]),
+        ]
+    }
+
+    #[test]
+    fn a_ticket_cannot_be_blocked_by_what_it_blocks() {
+        let mut tickets = chain();
+        assert!(matches!(
+            add_dependency(&mut tickets, "DIG-1", "DIG-3"),
+            Err(DomainError::DependencyCycle(id)) if id == "DIG-1"
+        ));
+        assert!(matches!(
+            add_dependency(&mut tickets, "DIG-1", "DIG-1"),
+            Err(DomainError::DependencyCycle(_))
+        ));
+        assert!(tickets[0].blocked_by.is_empty());
+
+        // 既にある関係は重複させない
+        add_dependency(&mut tickets, "DIG-3", "DIG-1").unwrap();
+        add_dependency(&mut tickets, "DIG-3", "DIG-1").unwrap();
+        assert_eq!(tickets[2].blocked_by, ["DIG-2", "DIG-1"]);
+    }
+
+    #[test]
+    fn loaded_cycles_and_missing_blockers_are_reported() {
+        assert!(validate_dependencies(&chain()).is_ok());
+
+        let mut tickets = chain();
+        tickets[0].blocked_by = vec!["DIG-3".to_string()];
+        assert!(matches!(
+            validate_dependencies(&tickets),
+            Err(DomainError::DependencyCycle(_))
+        ));
+
+        tickets[0].blocked_by = vec!["DIG-9".to_string()];
+        assert!(matches!(
+            validate_dependencies(&tickets),
+            Err(DomainError::TicketNotFound(id)) if id == "DIG-9"
+        ));
+    }
+
+    #[test]
+    fn only_open_blockers_remain() {
+        let mut tickets = chain();
+        tickets[2].blocked_by.push("DIG-1".to_string());
+        tickets[1].status = TicketStatus::Resolved;
+        let ids: Vec<&str> = open_blockers(&tickets, "DIG-3")
+            .iter()
+            .map(|ticket| ticket.id.as_str())
+            .collect();
+        assert_eq!(ids, ["DIG-1"]);
+        let blocked: Vec<&str> = blocks(&tickets, "DIG-1")
+            .map(|ticket| ticket.id.as_str())
+            .collect();
+        assert_eq!(blocked, ["DIG-2", "DIG-3"]);
+    }
+}
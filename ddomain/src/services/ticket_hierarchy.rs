@@ -0,0 +1,248 @@
+use crate::domain_errors::DomainError;
+use crate::entites::ticket::Ticket;
+use std::collections::HashSet;
+
+pub fn children_of<'a>(tickets: &'a [Ticket], id: &'a str) -> impl Iterator<Item = &'a Ticket> {
+    tickets
+        .iter()
+        .filter(move |ticket| ticket.parent.as_deref() == Some(id))
+}
+
+// 親をたどって指定したチケットに戻ってくる場合は循環とみなす
+fn leads_to_cycle(tickets: &[Ticket], child_id: &str, parent_id: &str) -> bool {
+    let mut visited = HashSet::new();
+    let mut current = Some(parent_id);
+    while let Some(id) = current {
+        if id == child_id || !visited.insert(id) {
+            return true;
+        }
+        current = tickets
+            .iter()
+            .find(|ticket| ticket.id == id)
+            .and_then(|ticket| ticket.parent.as_deref());
+    }
+    false
+}
+
+// 全チケットの親参照を検証する(存在しないID・循環参照を検出)
+pub fn validate_hierarchy(tickets: &[Ticket]) -> Result<(), DomainError> {
+    for ticket in tickets {
+        if let Some(parent_id) = ticket.parent.as_deref() {
+            if !tickets.iter().any(|candidate| candidate.id == parent_id) {
+                return Err(DomainError::TicketNotFound(parent_id.to_string()));
+            }
+            if leads_to_cycle(tickets, &ticket.id, parent_id) {
+                return Err(DomainError::HierarchyCycle(ticket.id.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn set_parent(
+    tickets: &mut [Ticket],
+    child_id: &str,
+    parent_id: Option<&str>,
+) -> Result<(), DomainError> {
+    if let Some(parent_id) = parent_id {
+        if !tickets.iter().any(|ticket| ticket.id == parent_id) {
+            return Err(DomainError::TicketNotFound(parent_id.to_string()));
+        }
+        if leads_to_cycle(tickets, child_id, parent_id) {
+            return Err(DomainError::HierarchyCycle(child_id.to_string()));
+        }
+    }
+    let child = tickets
+        .iter_mut()
+        .find(|ticket| ticket.id == child_id)
+        .ok_or_else(|| DomainError::TicketNotFound(child_id.to_string()))?;
+    child.parent = parent_id.map(str::to_string);
+    Ok(())
+}
+
+// 自身と全ての子孫のポイントの合計
+pub fn rolled_up_points(tickets: &[Ticket], id: &str) -> u32 {
+    let mut visited = HashSet::new();
+    rolled_up_points_inner(tickets, id, &mut visited)
+}
+
+fn rolled_up_points_inner<'a>(
+    tickets: &'a [Ticket],
+    id: &'a str,
+    visited: &mut HashSet<&'a str>,
+) -> u32 {
+    if !visited.insert(id) {
+        return 0;
+    }
+    let own = tickets
+        .iter()
+        .find(|ticket| ticket.id == id)
+        .map_or(0, |ticket| ticket.level.points());
+    own + children_of(tickets, id)
+        .map(|child| rolled_up_points_inner(tickets, &child.id, visited))
+        .sum::<u32>()
+}
+
+pub fn has_open_children(tickets: &[Ticket], id: &str) -> bool {
+    children_of(tickets, id).any(|child| child.is_open())
+}
+
+pub struct TreeEntry {
+    pub index: usize,
+    pub depth: usize,
+}
+
+// 指定したインデックスのチケットを木構造の順(深さ優先)に並べる。
+// 親が対象外のチケットは根として扱い、折りたたまれたチケットの子孫は含めない
+pub fn tree_order(
+    tickets: &[Ticket],
+    indices: &[usize],
+    collapsed: &HashSet<String>,
+) -> Vec<TreeEntry> {
+    let included: HashSet<&str> = indices.iter().map(|&i| tickets[i].id.as_str()).collect();
+    let mut entries = Vec::new();
+    let mut visited = HashSet::new();
+    for &index in indices {
+        let is_root = tickets[index]
+            .parent
+            .as_deref()
+            .is_none_or(|parent| !included.contains(parent));
+        if is_root {
+            push_subtree(
+                tickets,
+                indices,
+                collapsed,
+                index,
+                0,
+                &mut visited,
+                &mut entries,
+            );
+        }
+    }
+    // 親子関係が循環しているチケットは根にたどり着かないため、そこから並べる
+    for &index in indices {
+        let in_cycle = tickets[index]
+            .parent
+            .as_deref()
+            .is_some_and(|parent| leads_to_cycle(tickets, &tickets[index].id, parent));
+        if !in_cycle {
+            continue;
+        }
+        push_subtree(
+            tickets,
+            indices,
+            collapsed,
+            index,
+            0,
+            &mut visited,
+            &mut entries,
+        );
+    }
+    entries
+}
+
+fn push_subtree(
+    tickets: &[Ticket],
+    indices: &[usize],
+    collapsed: &HashSet<String>,
+    index: usize,
+    depth: usize,
+    visited: &mut HashSet<usize>,
+    entries: &mut Vec<TreeEntry>,
+) {
+    if !visited.insert(index) {
+        return;
+    }
+    entries.push(TreeEntry { index, depth });
+    if collapsed.contains(&tickets[index].id) {
+        return;
+    }
+    for &child in indices {
+        if tickets[child].parent.as_deref() == Some(tickets[index].id.as_str()) {
+            push_subtree(
+                tickets,
+                indices,
+                collapsed,
+                child,
+                depth + 1,
+                visited,
+                entries,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ticket_level::TicketLevel;
+    use crate::value_objects::ticket_status::TicketStatus;
+
+    fn ticket(id: &str, parent: Option<&str>, status: TicketStatus) -> Ticket {
+        let mut ticket = Ticket::new(
+            id.to_string(),
+            TicketLevel::Unestimated,
+            id.to_string(),
+            status,
+        );
+        ticket.parent = parent.map(str::to_string);
+        ticket
+    }
+
+    // DIG-1 の子が DIG-2、DIG-2 の子が DIG-3
+    fn chain() -> Vec<Ticket> {
+        vec![
+            ticket("DIG-1", None, TicketStatus::Pending),
+            ticket("DIG-2", Some("DIG-1"), TicketStatus::Pending),
+            ticket("DIG-3", Some("DIG-2"), TicketStatus::Pending),
+        ]
+    }
+
+    #[test]
+    fn a_descendant_cannot_become_the_parent() {
+        let mut tickets = chain();
+        assert!(matches!(
+            set_parent(&mut tickets, "DIG-1", Some("DIG-3")),
+            Err(DomainError::HierarchyCycle(id)) if id == "DIG-1"
+        ));
+        assert!(matches!(
+            set_parent(&mut tickets, "DIG-1", Some("DIG-1")),
+            Err(DomainError::HierarchyCycle(_))
+        ));
+        assert!(tickets[0].parent.is_none());
+
+        set_parent(&mut tickets, "DIG-3", Some("DIG-1")).unwrap();
+        assert_eq!(tickets[2].parent.as_deref(), Some("DIG-1"));
+    }
+
+    #[test]
+    fn loaded_cycles_and_missing_parents_are_reported() {
+        assert!(validate_hierarchy(&chain()).is_ok());
+
+        let mut tickets = chain();
+        tickets[0].parent = Some("DIG-3".to_string());
+        assert!(matches!(
+            validate_hierarchy(&tickets),
+            Err(DomainError::HierarchyCycle(_))
+        ));
+
+        tickets[0].parent = Some("DIG-9".to_string());
+        assert!(matches!(
+            validate_hierarchy(&tickets),
+            Err(DomainError::TicketNotFound(id)) if id == "DIG-9"
+        ));
+    }
+
+    #[test]
+    fn only_open_direct_children_count() {
+        let mut tickets = chain();
+        assert!(has_open_children(&tickets, "DIG-1"));
+        assert!(!has_open_children(&tickets, "DIG-3"));
+
+        // 孫が未完了でも、子が完了していれば親は完了にできる
+        tickets[1].status = TicketStatus::Resolved;
+        assert!(!has_open_children(&tickets, "DIG-1"));
+        tickets[1].status = TicketStatus::Canceled;
+        assert!(!has_open_children(&tickets, "DIG-1"));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::entites::ticket::Ticket;
+
+// IDの末尾の数字を除いた部分("DIG-12" なら "DIG-")
+pub fn prefix_of(id: &str) -> &str {
+    id.trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
+// 同じ接頭辞を持つチケットの番号の最大値に1を足したID
+pub fn next_id(tickets: &[Ticket], prefix: &str) -> String {
+    next_free_id(tickets.iter().map(|ticket| ticket.id.as_str()), prefix)
+}
+
+// IDの一覧から求める next_id
+pub fn next_free_id<'a>(ids: impl IntoIterator<Item = &'a str>, prefix: &str) -> String {
+    let max = ids
+        .into_iter()
+        .filter_map(|id| id.strip_prefix(prefix))
+        .filter_map(|number| number.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{}{}", prefix, max + 1)
+}
+
+// ファイルで使われている接頭辞。チケットが無い場合は "DIG-"
+pub fn file_prefix(tickets: &[Ticket]) -> &str {
+    tickets
+        .first()
+        .map_or("DIG-", |ticket| prefix_of(&ticket.id))
+}
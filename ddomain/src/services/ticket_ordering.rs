@@ -0,0 +1,62 @@
+use crate::domain_errors::DomainError;
+use crate::entites::ticket::Ticket;
+use std::cmp::Ordering;
+
+// 順位が未設定(0)のチケットは順位を持つチケットの後ろに並べる
+fn rank_key(ticket: &Ticket) -> u32 {
+    if ticket.rank == 0 {
+        u32::MAX
+    } else {
+        ticket.rank
+    }
+}
+
+// 既定の並び順: 優先度 -> 優先度内の手動の順位 -> 作成日時の古い順
+pub fn compare(a: &Ticket, b: &Ticket) -> Ordering {
+    a.priority
+        .cmp(&b.priority)
+        .then(rank_key(a).cmp(&rank_key(b)))
+        .then(a.created_at.cmp(&b.created_at))
+}
+
+// インデックスを既定の並び順に並べ替える
+pub fn sort_indices(tickets: &[Ticket], indices: &mut [usize]) {
+    indices.sort_by(|&a, &b| compare(&tickets[a], &tickets[b]));
+}
+
+// 同じ親・同じ優先度のチケットの中で順位を1つ上(up)または下に移動する。
+// 順位は並び順どおりに振り直して保存する。移動できなかった場合は false
+pub fn move_within_priority(
+    tickets: &mut [Ticket],
+    id: &str,
+    up: bool,
+) -> Result<bool, DomainError> {
+    let target = tickets
+        .iter()
+        .find(|ticket| ticket.id == id)
+        .ok_or_else(|| DomainError::TicketNotFound(id.to_string()))?;
+    let (parent, priority) = (target.parent.clone(), target.priority);
+
+    let mut group: Vec<usize> = (0..tickets.len())
+        .filter(|&i| tickets[i].parent == parent && tickets[i].priority == priority)
+        .collect();
+    sort_indices(tickets, &mut group);
+    let position = group
+        .iter()
+        .position(|&i| tickets[i].id == id)
+        .unwrap_or_default();
+    let swap_with = if up {
+        position.checked_sub(1)
+    } else {
+        Some(position + 1).filter(|&next| next < group.len())
+    };
+    let Some(swap_with) = swap_with else {
+        return Ok(false);
+    };
+    group.swap(position, swap_with);
+
+    for (rank, &i) in group.iter().enumerate() {
+        tickets[i].rank = rank as u32 + 1;
+    }
+    Ok(true)
+}
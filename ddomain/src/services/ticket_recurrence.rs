@@ -0,0 +1,163 @@
+use crate::entites::ticket::Ticket;
+use crate::entites::workflow::Workflow;
+use crate::services::ticket_ids;
+use chrono::{Duration, NaiveDate};
+
+// 繰り返しの規則を持つチケットから、指定した期日の次のチケットを作る。
+// 規則は新しいチケットに引き継ぎ、元のチケットからは取り除く。戻り値は新しいチケットのID
+fn spawn(tickets: &mut Vec<Ticket>, index: usize, due: NaiveDate, workflow: &Workflow) -> String {
+    let template = &tickets[index];
+    let id = ticket_ids::next_id(tickets, ticket_ids::prefix_of(&template.id));
+    let mut next = Ticket::new(
+        id.clone(),
+        template.level.clone(),
+        template.title.clone(),
+        workflow.initial_status(),
+    );
+    next.priority = template.priority;
+    next.parent = template.parent.clone();
+    next.tags = template.tags.clone();
+    next.assignee = template.assignee.clone();
+    next.fields = template.fields.clone();
+    next.project = template.project.clone();
+    next.due = Some(due);
+    next.recurrence = tickets[index].recurrence.take();
+    tickets.push(next);
+    id
+}
+
+// 繰り返しのチケットが完了したときに次のチケットを作る。
+// 次の期日は元の期日の次の発生日とし、それが過去になる場合は今日以降の発生日にする
+pub fn spawn_next(
+    tickets: &mut Vec<Ticket>,
+    id: &str,
+    workflow: &Workflow,
+    today: NaiveDate,
+) -> Option<String> {
+    let index = tickets.iter().position(|ticket| ticket.id == id)?;
+    let recurrence = tickets[index].recurrence.clone()?;
+    let base = tickets[index].due.unwrap_or(today);
+    let due = recurrence
+        .next_after(base)
+        .filter(|due| *due >= today)
+        .or_else(|| recurrence.next_after(today - Duration::days(1)))?;
+    Some(spawn(tickets, index, due, workflow))
+}
+
+// 未完了の繰り返しのチケットのうち、期日より後の発生日が今日までに来ているものについて
+// 直近の発生日を期日とするチケットを作る。キャンセルなどで閉じたチケットの繰り返しは止まる。
+// 戻り値は作成したチケットのID
+pub fn materialize_due(
+    tickets: &mut Vec<Ticket>,
+    workflow: &Workflow,
+    today: NaiveDate,
+) -> Vec<String> {
+    let holders: Vec<usize> = tickets
+        .iter()
+        .enumerate()
+        .filter(|(_, ticket)| ticket.is_open() && ticket.recurrence.is_some())
+        .map(|(i, _)| i)
+        .collect();
+    let mut spawned = Vec::new();
+    for index in holders {
+        let Some(recurrence) = tickets[index].recurrence.clone() else {
+            continue;
+        };
+        let since = tickets[index]
+            .due
+            .unwrap_or_else(|| tickets[index].created_at.date_naive());
+        if let Some(due) = recurrence
+            .latest_until(today)
+            .filter(|latest| *latest > since)
+        {
+            spawned.push(spawn(tickets, index, due, workflow));
+        }
+    }
+    spawned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::recurrence::Recurrence;
+    use crate::value_objects::ticket_level::TicketLevel;
+    use crate::value_objects::ticket_status::TicketStatus;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    // 2026-10-12 は月曜日
+    fn weekly(id: &str, due: &str, status: TicketStatus) -> Ticket {
+        let mut ticket = Ticket::new(
+            id.to_string(),
+            TicketLevel::Unestimated,
+            "Weekly report".to_string(),
+            status,
+        );
+        ticket.due = Some(date(due));
+        ticket.recurrence = Some(Recurrence::parse("weekly:mon").unwrap());
+        ticket
+    }
+
+    #[test]
+    fn the_next_occurrence_takes_over_the_rule() {
+        let mut tickets = vec![weekly("DIG-1", "2026-10-12", TicketStatus::Resolved)];
+        let spawned = spawn_next(
+            &mut tickets,
+            "DIG-1",
+            &Workflow::default(),
+            date("2026-10-14"),
+        );
+
+        assert_eq!(spawned.as_deref(), Some("DIG-2"));
+        assert!(tickets[0].recurrence.is_none());
+        let next = &tickets[1];
+        assert_eq!(next.due, Some(date("2026-10-19")));
+        assert_eq!(next.status, TicketStatus::Pending);
+        assert_eq!(next.title, "Weekly report");
+        assert!(next.recurrence.is_some());
+    }
+
+    #[test]
+    fn an_overdue_ticket_continues_from_today() {
+        let mut tickets = vec![weekly("DIG-1", "2026-09-07", TicketStatus::Resolved)];
+        spawn_next(
+            &mut tickets,
+            "DIG-1",
+            &Workflow::default(),
+            date("2026-10-14"),
+        );
+        assert_eq!(tickets[1].due, Some(date("2026-10-19")));
+    }
+
+    #[test]
+    fn tickets_without_a_rule_spawn_nothing() {
+        let mut tickets = vec![weekly("DIG-1", "2026-10-12", TicketStatus::Resolved)];
+        tickets[0].recurrence = None;
+        let spawned = spawn_next(
+            &mut tickets,
+            "DIG-1",
+            &Workflow::default(),
+            date("2026-10-14"),
+        );
+        assert_eq!(spawned, None);
+        assert_eq!(tickets.len(), 1);
+    }
+
+    #[test]
+    fn only_open_tickets_past_an_occurrence_are_materialized() {
+        let mut tickets = vec![
+            weekly("DIG-1", "2026-10-05", TicketStatus::Pending),
+            weekly("DIG-2", "2026-10-12", TicketStatus::Pending),
+            weekly("DIG-3", "2026-10-05", TicketStatus::Canceled),
+        ];
+        let spawned = materialize_due(&mut tickets, &Workflow::default(), date("2026-10-14"));
+
+        assert_eq!(spawned, ["DIG-4"]);
+        assert_eq!(tickets[3].due, Some(date("2026-10-12")));
+        assert!(tickets[0].recurrence.is_none());
+        assert!(tickets[1].recurrence.is_some());
+        assert!(tickets[2].recurrence.is_some());
+    }
+}
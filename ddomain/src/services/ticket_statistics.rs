@@ -0,0 +1,164 @@
+use crate::entites::member::Member;
+use crate::entites::ticket::Ticket;
+use crate::value_objects::chart_window::ChartWindow;
+use crate::value_objects::status_category::StatusCategory;
+use chrono::{DateTime, Duration, Utc};
+
+pub struct BurndownPoint {
+    pub at: DateTime<Utc>,
+    pub remaining_points: u32,
+}
+
+pub struct VelocityBucket {
+    pub week_start: DateTime<Utc>,
+    pub resolved_points: u32,
+}
+
+// ステータスの分類ごとのチケット数
+pub struct CumulativeFlowPoint {
+    pub at: DateTime<Utc>,
+    pub todo: u32,
+    pub in_progress: u32,
+    pub done: u32,
+}
+
+// 集計期間の開始時刻を求める(Allの場合は最古のチケットの作成日時)
+pub fn window_start(tickets: &[Ticket], window: ChartWindow, now: DateTime<Utc>) -> DateTime<Utc> {
+    match window.duration() {
+        Some(duration) => now - duration,
+        None => tickets
+            .iter()
+            .map(|ticket| ticket.created_at)
+            .min()
+            .unwrap_or(now),
+    }
+}
+
+// 開始時刻から現在まで1日刻みのサンプリング時刻を返す(末尾は必ず現在時刻)
+fn sample_points(start: DateTime<Utc>, now: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let mut points = Vec::new();
+    let mut at = start;
+    while at < now {
+        points.push(at);
+        at += Duration::days(1);
+    }
+    points.push(now);
+    points
+}
+
+pub fn burndown(
+    tickets: &[Ticket],
+    start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<BurndownPoint> {
+    sample_points(start, now)
+        .into_iter()
+        .map(|at| BurndownPoint {
+            at,
+            remaining_points: tickets
+                .iter()
+                .filter(|ticket| ticket.created_at <= at)
+                .filter(|ticket| ticket.status.category() != StatusCategory::Canceled)
+                .filter(|ticket| !ticket.is_resolved_at(at))
+                .map(|ticket| ticket.level.points())
+                .sum(),
+        })
+        .collect()
+}
+
+pub fn velocity(
+    tickets: &[Ticket],
+    start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<VelocityBucket> {
+    let mut buckets = Vec::new();
+    let mut week_start = start;
+    while week_start < now {
+        let week_end = week_start + Duration::weeks(1);
+        let resolved_points = tickets
+            .iter()
+            .filter(|ticket| ticket.status.category() == StatusCategory::Done)
+            .filter(|ticket| {
+                ticket
+                    .resolved_at
+                    .is_some_and(|at| at >= week_start && at < week_end)
+            })
+            .map(|ticket| ticket.level.points())
+            .sum();
+        buckets.push(VelocityBucket {
+            week_start,
+            resolved_points,
+        });
+        week_start = week_end;
+    }
+    buckets
+}
+
+// ステータス履歴を保持していないため、未完了のチケットは現在の分類で、
+// 後に完了したチケットは完了前の期間を作業中として数える
+pub fn cumulative_flow(
+    tickets: &[Ticket],
+    start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<CumulativeFlowPoint> {
+    sample_points(start, now)
+        .into_iter()
+        .map(|at| {
+            let mut point = CumulativeFlowPoint {
+                at,
+                todo: 0,
+                in_progress: 0,
+                done: 0,
+            };
+            for ticket in tickets.iter().filter(|ticket| ticket.created_at <= at) {
+                if ticket.is_resolved_at(at) {
+                    point.done += 1;
+                    continue;
+                }
+                match ticket.status.category() {
+                    StatusCategory::Todo => point.todo += 1,
+                    StatusCategory::InProgress | StatusCategory::Done => point.in_progress += 1,
+                    StatusCategory::Canceled => {}
+                }
+            }
+            point
+        })
+        .collect()
+}
+
+pub struct Workload {
+    // 未割り当ての場合は None
+    pub assignee: Option<String>,
+    pub open_points: u32,
+    pub open_tickets: usize,
+}
+
+// 担当者ごとの未完了ポイント。名簿のメンバーは0件でも含め、名簿外の担当者と未割り当ては末尾に追加する
+pub fn workload_by_assignee(tickets: &[Ticket], members: &[Member]) -> Vec<Workload> {
+    let mut assignees: Vec<Option<String>> = members
+        .iter()
+        .map(|member| Some(member.id.clone()))
+        .collect();
+    for ticket in tickets.iter().filter(|ticket| ticket.is_open()) {
+        if !assignees.contains(&ticket.assignee) {
+            assignees.push(ticket.assignee.clone());
+        }
+    }
+    // 未割り当ては常に末尾
+    assignees.sort_by_key(|assignee| assignee.is_none());
+
+    assignees
+        .into_iter()
+        .map(|assignee| {
+            let open: Vec<&Ticket> = tickets
+                .iter()
+                .filter(|ticket| ticket.is_open() && ticket.assignee == assignee)
+                .collect();
+            Workload {
+                open_points: open.iter().map(|ticket| ticket.level.points()).sum(),
+                open_tickets: open.len(),
+                assignee,
+            }
+        })
+        .collect()
+}
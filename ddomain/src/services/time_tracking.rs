@@ -0,0 +1,44 @@
+use crate::entites::ticket::Ticket;
+use crate::value_objects::timesheet_grouping::TimesheetGrouping;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+pub struct TimesheetRow {
+    pub key: String,
+    pub duration: Duration,
+}
+
+// 開始日が from..=to に含まれる作業時間を集計単位ごとに合計する(キーの昇順)
+pub fn timesheet(
+    tickets: &[Ticket],
+    from: NaiveDate,
+    to: NaiveDate,
+    grouping: TimesheetGrouping,
+    now: DateTime<Utc>,
+) -> Vec<TimesheetRow> {
+    let mut rows: Vec<TimesheetRow> = Vec::new();
+    for ticket in tickets {
+        for entry in &ticket.time_entries {
+            let day = entry.start.date_naive();
+            if day < from || day > to {
+                continue;
+            }
+            let key = match grouping {
+                TimesheetGrouping::Ticket => ticket.id.clone(),
+                TimesheetGrouping::Person => entry
+                    .member
+                    .clone()
+                    .unwrap_or_else(|| "(unknown)".to_string()),
+                TimesheetGrouping::Day => day.to_string(),
+            };
+            match rows.iter_mut().find(|row| row.key == key) {
+                Some(row) => row.duration += entry.duration(now),
+                None => rows.push(TimesheetRow {
+                    key,
+                    duration: entry.duration(now),
+                }),
+            }
+        }
+    }
+    rows.sort_by(|a, b| a.key.cmp(&b.key));
+    rows
+}
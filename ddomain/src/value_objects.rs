@@ -1,3 +1,15 @@
 pub mod app_mode;
+pub mod chart_window;
+pub mod due_bucket;
+pub mod estimation_scale;
+pub mod field_condition;
+pub mod field_type;
+pub mod field_value;
+pub mod priority;
+pub mod recurrence;
+pub mod source_location;
+pub mod status_category;
+pub mod ticket_filter;
 pub mod ticket_level;
 pub mod ticket_status;
+pub mod timesheet_grouping;
@@ -2,4 +2,8 @@
 pub enum AppMode {
     Normal,
     Edit,
+    Chart,
+    Input,
+    Agenda,
+    Board,
 }
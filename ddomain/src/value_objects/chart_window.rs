@@ -0,0 +1,43 @@
+use chrono::Duration;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ChartWindow {
+    #[default]
+    TwoWeeks,
+    FourWeeks,
+    Quarter,
+    All,
+}
+
+impl ChartWindow {
+    // 表示期間を順番に切り替える
+    pub fn next(self) -> Self {
+        match self {
+            ChartWindow::TwoWeeks => ChartWindow::FourWeeks,
+            ChartWindow::FourWeeks => ChartWindow::Quarter,
+            ChartWindow::Quarter => ChartWindow::All,
+            ChartWindow::All => ChartWindow::TwoWeeks,
+        }
+    }
+
+    // Allの場合は最古のチケットから集計するため期間を持たない
+    pub fn duration(self) -> Option<Duration> {
+        match self {
+            ChartWindow::TwoWeeks => Some(Duration::weeks(2)),
+            ChartWindow::FourWeeks => Some(Duration::weeks(4)),
+            ChartWindow::Quarter => Some(Duration::weeks(13)),
+            ChartWindow::All => None,
+        }
+    }
+}
+
+impl From<ChartWindow> for String {
+    fn from(window: ChartWindow) -> Self {
+        match window {
+            ChartWindow::TwoWeeks => "2 weeks".to_string(),
+            ChartWindow::FourWeeks => "4 weeks".to_string(),
+            ChartWindow::Quarter => "Quarter".to_string(),
+            ChartWindow::All => "All".to_string(),
+        }
+    }
+}
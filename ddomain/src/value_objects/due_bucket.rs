@@ -0,0 +1,43 @@
+use chrono::{Duration, NaiveDate};
+
+// アジェンダ表示での期日の区分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DueBucket {
+    Overdue,
+    Today,
+    ThisWeek,
+    Later,
+}
+
+impl DueBucket {
+    pub const ALL: [DueBucket; 4] = [
+        DueBucket::Overdue,
+        DueBucket::Today,
+        DueBucket::ThisWeek,
+        DueBucket::Later,
+    ];
+
+    // 今日から7日以内を今週とみなす
+    pub fn classify(due: NaiveDate, today: NaiveDate) -> Self {
+        if due < today {
+            DueBucket::Overdue
+        } else if due == today {
+            DueBucket::Today
+        } else if due <= today + Duration::days(7) {
+            DueBucket::ThisWeek
+        } else {
+            DueBucket::Later
+        }
+    }
+}
+
+impl From<DueBucket> for String {
+    fn from(bucket: DueBucket) -> Self {
+        match bucket {
+            DueBucket::Overdue => "Overdue".to_string(),
+            DueBucket::Today => "Today".to_string(),
+            DueBucket::ThisWeek => "This week".to_string(),
+            DueBucket::Later => "Later".to_string(),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::value_objects::ticket_level::TicketLevel;
+use serde::{Deserialize, Serialize};
+
+// ファイル単位の見積もりのスケール
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EstimationScale {
+    #[default]
+    Fibonacci,
+    PowersOfTwo,
+    TShirt,
+    // ラベルは作業時間(時間単位の整数)で、時間をそのままポイントとする
+    Hours,
+}
+
+impl EstimationScale {
+    // 選択肢のラベルと既定のポイント(末尾は未見積もり)
+    fn choices(&self) -> Vec<(&'static str, u32)> {
+        match self {
+            EstimationScale::Fibonacci => vec![
+                ("One", 1),
+                ("Two", 2),
+                ("Three", 3),
+                ("Five", 5),
+                ("Eight", 8),
+                ("Thirteen", 13),
+                ("TwentyOne", 21),
+            ],
+            EstimationScale::PowersOfTwo => vec![
+                ("1", 1),
+                ("2", 2),
+                ("4", 4),
+                ("8", 8),
+                ("16", 16),
+                ("32", 32),
+            ],
+            EstimationScale::TShirt => {
+                vec![("XS", 1), ("S", 2), ("M", 3), ("L", 5), ("XL", 8)]
+            }
+            EstimationScale::Hours => vec![
+                ("1", 1),
+                ("2", 2),
+                ("4", 4),
+                ("8", 8),
+                ("16", 16),
+                ("24", 24),
+                ("40", 40),
+            ],
+        }
+    }
+
+    // TUIで切り替える順の見積もり
+    pub fn levels(&self) -> Vec<TicketLevel> {
+        self.choices()
+            .into_iter()
+            .map(|(label, points)| TicketLevel::with_points(label, points))
+            .chain(std::iter::once(TicketLevel::Unestimated))
+            .collect()
+    }
+
+    // スケールに含まれるラベルの既定のポイント。時間のスケールは任意の整数を受け付ける
+    pub fn points(&self, label: &str) -> Option<u32> {
+        if label == "?" {
+            return Some(0);
+        }
+        self.choices()
+            .into_iter()
+            .find(|(choice, _)| *choice == label)
+            .map(|(_, points)| points)
+            .or_else(|| {
+                (*self == EstimationScale::Hours)
+                    .then(|| label.parse().ok())
+                    .flatten()
+            })
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == EstimationScale::default()
+    }
+}
@@ -0,0 +1,78 @@
+use crate::entites::ticket::Ticket;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    // 長い演算子を先に判定する
+    pub const ALL: [(&'static str, Comparison); 6] = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        ("!=", Comparison::NotEqual),
+        ("=", Comparison::Equal),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+    ];
+
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Equal => ordering == Ordering::Equal,
+            Comparison::NotEqual => ordering != Ordering::Equal,
+            Comparison::Greater => ordering == Ordering::Greater,
+            Comparison::GreaterOrEqual => ordering != Ordering::Less,
+            Comparison::Less => ordering == Ordering::Less,
+            Comparison::LessOrEqual => ordering != Ordering::Greater,
+        }
+    }
+}
+
+// "severity>2" のようなフィールドに対する条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCondition {
+    pub field: String,
+    pub comparison: Comparison,
+    pub value: String,
+}
+
+// 両方が数値の場合は数値として、それ以外は文字列として比較する(日付はISO形式のため文字列順で比較できる)
+fn compare(actual: &str, expected: &str) -> Ordering {
+    match (actual.parse::<f64>(), expected.parse::<f64>()) {
+        (Ok(actual), Ok(expected)) => actual.partial_cmp(&expected).unwrap_or(Ordering::Equal),
+        _ => actual.cmp(expected),
+    }
+}
+
+impl FieldCondition {
+    // 値を持たないチケットは != の場合のみ一致する
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        let values = ticket.field_texts(&self.field);
+        if values.is_empty() {
+            return self.comparison == Comparison::NotEqual;
+        }
+        match self.comparison {
+            // 複数の値を持つフィールド(タグ)は、どれとも一致しない場合に != を満たす
+            Comparison::NotEqual => values.iter().all(|value| value != &self.value),
+            comparison => values
+                .iter()
+                .any(|value| comparison.accepts(compare(value, &self.value))),
+        }
+    }
+}
+
+impl From<&FieldCondition> for String {
+    fn from(condition: &FieldCondition) -> Self {
+        let operator = Comparison::ALL
+            .iter()
+            .find(|(_, comparison)| *comparison == condition.comparison)
+            .map_or("=", |(operator, _)| *operator);
+        format!("{}{}{}", condition.field, operator, condition.value)
+    }
+}
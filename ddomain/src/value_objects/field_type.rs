@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+// ファイルヘッダーで宣言するカスタムフィールドの型
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    #[default]
+    String,
+    Number,
+    Enum,
+    Date,
+    Bool,
+}
+
+impl From<FieldType> for String {
+    fn from(field_type: FieldType) -> Self {
+        match field_type {
+            FieldType::String => "string".to_string(),
+            FieldType::Number => "number".to_string(),
+            FieldType::Enum => "enum".to_string(),
+            FieldType::Date => "date".to_string(),
+            FieldType::Bool => "bool".to_string(),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// カスタムフィールドの値。列挙値と日付("2024-05-01")は文字列として保存する
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FieldValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Bool(value) => write!(f, "{}", value),
+            FieldValue::Number(value) => write!(f, "{}", value),
+            FieldValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
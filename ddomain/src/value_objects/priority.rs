@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+// 重要度。宣言順(Critical が先頭)に並べる
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Critical,
+    High,
+    #[default]
+    Medium,
+    Low,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [
+        Priority::Critical,
+        Priority::High,
+        Priority::Medium,
+        Priority::Low,
+    ];
+
+    // 名前が一致する重要度。一致しない場合は None
+    pub fn parse(name: &str) -> Option<Self> {
+        Priority::ALL
+            .into_iter()
+            .find(|priority| String::from(*priority) == name)
+    }
+
+    // TUIでの切り替え順
+    pub fn next(&self) -> Self {
+        match self {
+            Priority::Critical => Priority::High,
+            Priority::High => Priority::Medium,
+            Priority::Medium => Priority::Low,
+            Priority::Low => Priority::Critical,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Priority::default()
+    }
+}
+
+impl From<String> for Priority {
+    fn from(s: String) -> Self {
+        Priority::parse(&s).unwrap_or_default()
+    }
+}
+
+impl From<Priority> for String {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Critical => "Critical".to_string(),
+            Priority::High => "High".to_string(),
+            Priority::Medium => "Medium".to_string(),
+            Priority::Low => "Low".to_string(),
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use crate::domain_errors::DomainError;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+// 繰り返しの規則。ファイルには "daily", "weekly:mon", "monthly:15", "cron:0 9 * * 1-5" の形式で保存する
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum Recurrence {
+    Daily,
+    Weekly(Weekday),
+    // 月の日数より大きい日は月末として扱う
+    Monthly(u32),
+    // 分と時は期日に影響しないため、日・月・曜日の欄のみを使う
+    Cron(CronSchedule),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    expression: String,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    // 0 が日曜日
+    days_of_week: Vec<u32>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+// "*", "1-5", "1,15", "*/2", "10-20/5" の形式の欄を値の一覧に展開する
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse().ok().filter(|&step| step > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let value = range.parse().ok()?;
+                    (value, if step > 1 { max } else { value })
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    Some(values)
+}
+
+impl CronSchedule {
+    fn parse(expression: &str) -> Option<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return None;
+        };
+        parse_cron_field(minute, 0, 59)?;
+        parse_cron_field(hour, 0, 23)?;
+        Some(CronSchedule {
+            expression: fields.join(" "),
+            days_of_month: parse_cron_field(day_of_month, 1, 31)?,
+            months: parse_cron_field(month, 1, 12)?,
+            // 7 も日曜日として受け付ける
+            days_of_week: parse_cron_field(day_of_week, 0, 7)?
+                .into_iter()
+                .map(|day| day % 7)
+                .collect(),
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    // cron と同様に、日と曜日の両方が指定された場合はどちらかに一致すればよい
+    fn matches(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.days_of_month.contains(&date.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&date.weekday().num_days_from_sunday());
+        let day = match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+        day && self.months.contains(&date.month())
+    }
+}
+
+impl Recurrence {
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        let input = input.trim();
+        let invalid = || DomainError::InvalidRecurrence(input.to_string());
+        let lower = input.to_lowercase();
+        let (kind, argument) = lower.split_once(':').unwrap_or((&lower, ""));
+        match kind.trim() {
+            "daily" => Ok(Recurrence::Daily),
+            "weekly" => argument
+                .trim()
+                .parse()
+                .map(Recurrence::Weekly)
+                .map_err(|_| invalid()),
+            "monthly" => argument
+                .trim()
+                .parse()
+                .ok()
+                .filter(|day| (1..=31).contains(day))
+                .map(Recurrence::Monthly)
+                .ok_or_else(invalid),
+            "cron" => CronSchedule::parse(argument)
+                .map(Recurrence::Cron)
+                .ok_or_else(invalid),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        match self {
+            Recurrence::Daily => true,
+            Recurrence::Weekly(weekday) => date.weekday() == *weekday,
+            Recurrence::Monthly(day) => {
+                let last_day = date
+                    .with_day(1)
+                    .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+                    .and_then(|next| next.pred_opt())
+                    .map_or(31, |last| last.day());
+                date.day() == (*day).min(last_day)
+            }
+            Recurrence::Cron(schedule) => schedule.matches(date),
+        }
+    }
+
+    // 指定した日より後の最初の発生日(5年以内に無い場合は None)
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        (1..=366 * 5)
+            .map(|days| date + Duration::days(days))
+            .find(|candidate| self.matches(*candidate))
+    }
+
+    // 指定した日以前の最後の発生日
+    pub fn latest_until(&self, date: NaiveDate) -> Option<NaiveDate> {
+        (0..=366 * 5)
+            .map(|days| date - Duration::days(days))
+            .find(|candidate| self.matches(*candidate))
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = DomainError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Recurrence::parse(&s)
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(recurrence: Recurrence) -> Self {
+        match recurrence {
+            Recurrence::Daily => "daily".to_string(),
+            Recurrence::Weekly(weekday) => {
+                format!("weekly:{}", weekday.to_string().to_lowercase())
+            }
+            Recurrence::Monthly(day) => format!("monthly:{}", day),
+            Recurrence::Cron(schedule) => format!("cron:{}", schedule.expression),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn next(rule: &str, after: &str) -> Option<NaiveDate> {
+        Recurrence::parse(rule).unwrap().next_after(date(after))
+    }
+
+    #[test]
+    fn rules_round_trip_in_their_stored_form() {
+        for (input, stored) in [
+            ("daily", "daily"),
+            ("Weekly: Mon", "weekly:mon"),
+            ("monthly:15", "monthly:15"),
+            ("cron:0  9 * * 1-5", "cron:0 9 * * 1-5"),
+        ] {
+            let recurrence = Recurrence::parse(input).unwrap();
+            assert_eq!(String::from(recurrence), stored);
+        }
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        for input in [
+            "yearly",
+            "weekly:someday",
+            "monthly:0",
+            "monthly:32",
+            "cron:0 9 * *",
+            "cron:*/0 * * * *",
+            "cron:0 24 * * *",
+            "cron:0 9 0 * *",
+            "cron:0 9 * 13 *",
+            "cron:0 9 * * 5-1",
+        ] {
+            assert!(
+                matches!(
+                    Recurrence::parse(input),
+                    Err(DomainError::InvalidRecurrence(_))
+                ),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn monthly_days_past_the_end_of_the_month_fall_on_the_last_day() {
+        assert_eq!(next("monthly:31", "2026-01-31"), Some(date("2026-02-28")));
+        assert_eq!(next("monthly:31", "2026-02-28"), Some(date("2026-03-31")));
+        assert_eq!(next("monthly:30", "2028-02-01"), Some(date("2028-02-29")));
+    }
+
+    // 2026-10-16 は金曜日
+    #[test]
+    fn cron_weekdays_skip_the_weekend() {
+        assert_eq!(
+            next("cron:0 9 * * 1-5", "2026-10-16"),
+            Some(date("2026-10-19"))
+        );
+        assert_eq!(
+            next("cron:0 9 * * 1-5", "2026-10-19"),
+            Some(date("2026-10-20"))
+        );
+        // 7 も日曜日
+        assert_eq!(
+            next("cron:0 0 * * 7", "2026-10-16"),
+            Some(date("2026-10-18"))
+        );
+    }
+
+    #[test]
+    fn cron_steps_and_day_or_weekday() {
+        assert_eq!(
+            next("cron:0 0 */10 * *", "2026-10-18"),
+            Some(date("2026-10-21"))
+        );
+        assert_eq!(
+            next("cron:0 0 */10 * *", "2026-10-21"),
+            Some(date("2026-10-31"))
+        );
+        // 日と曜日の両方を指定した場合はどちらかに一致する日(11月1日は日曜日)
+        assert_eq!(
+            next("cron:0 0 1 * 1", "2026-10-27"),
+            Some(date("2026-11-01"))
+        );
+        assert_eq!(
+            next("cron:0 0 1 * 1", "2026-11-01"),
+            Some(date("2026-11-02"))
+        );
+        assert_eq!(
+            next("cron:0 0 1 1 *", "2026-10-18"),
+            Some(date("2027-01-01"))
+        );
+    }
+
+    #[test]
+    fn latest_until_includes_the_day_itself() {
+        let weekly = Recurrence::parse("weekly:mon").unwrap();
+        assert_eq!(
+            weekly.latest_until(date("2026-10-18")),
+            Some(date("2026-10-12"))
+        );
+        assert_eq!(
+            weekly.latest_until(date("2026-10-19")),
+            Some(date("2026-10-19"))
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use crate::domain_errors::DomainError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// ソースコード中の位置。ファイルには "src/main.rs:42" の形式で保存する
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct SourceLocation {
+    // 走査したディレクトリを含む、区切りを / にそろえたパス
+    pub path: String,
+    pub line: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.path, self.line)
+    }
+}
+
+impl TryFrom<String> for SourceLocation {
+    type Error = DomainError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.rsplit_once(':')
+            .and_then(|(path, line)| {
+                Some(SourceLocation {
+                    path: path.to_string(),
+                    line: line.parse().ok().filter(|&line| line > 0)?,
+                })
+            })
+            .filter(|location| !location.path.is_empty())
+            .ok_or(DomainError::InvalidSourceLocation(s))
+    }
+}
+
+impl From<SourceLocation> for String {
+    fn from(location: SourceLocation) -> Self {
+        location.to_string()
+    }
+}
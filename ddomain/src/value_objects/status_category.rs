@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+// ステータスの分類。統計や未完了の判定はステータス名ではなく分類で行う
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusCategory {
+    #[default]
+    Todo,
+    InProgress,
+    Done,
+    // 作業せずに閉じたもの(ポイントを消化しない)
+    Canceled,
+}
+
+impl StatusCategory {
+    pub fn is_open(&self) -> bool {
+        matches!(self, StatusCategory::Todo | StatusCategory::InProgress)
+    }
+}
+
+impl From<String> for StatusCategory {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "in-progress" => StatusCategory::InProgress,
+            "done" => StatusCategory::Done,
+            "canceled" => StatusCategory::Canceled,
+            _ => StatusCategory::Todo,
+        }
+    }
+}
+
+impl From<StatusCategory> for String {
+    fn from(category: StatusCategory) -> Self {
+        match category {
+            StatusCategory::Todo => "todo".to_string(),
+            StatusCategory::InProgress => "in-progress".to_string(),
+            StatusCategory::Done => "done".to_string(),
+            StatusCategory::Canceled => "canceled".to_string(),
+        }
+    }
+}
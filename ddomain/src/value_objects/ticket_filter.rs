@@ -0,0 +1,28 @@
+use crate::entites::ticket::Ticket;
+use crate::value_objects::field_condition::FieldCondition;
+
+// 一覧表示の絞り込み条件。指定された条件をすべて満たすチケットが対象
+#[derive(Debug, Default, Clone)]
+pub struct TicketFilter {
+    pub tags: Vec<String>,
+    pub assignee: Option<String>,
+    pub conditions: Vec<FieldCondition>,
+}
+
+impl TicketFilter {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.assignee.is_none() && self.conditions.is_empty()
+    }
+
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        self.tags.iter().all(|tag| ticket.tags.contains(tag))
+            && self
+                .assignee
+                .as_ref()
+                .is_none_or(|assignee| ticket.assignee.as_ref() == Some(assignee))
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(ticket))
+    }
+}
@@ -1,16 +1,71 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+// 見積もり。ファイルにはラベルのみを保存し、独自のスケールのポイントは読み込み時にファイルヘッダーから補う
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(from = "String", into = "String")]
 pub enum TicketLevel {
     #[default]
-    One = 1,
+    One,
     Two,
     Three,
     Five,
     Eight,
     Thirteen,
+    TwentyOne,
+    // 未見積もり("?")。ポイントは0として扱う
+    Unestimated,
+    // 2の累乗・Tシャツサイズ・時間などのスケールのラベル
+    Custom {
+        label: String,
+        points: u32,
+    },
 }
 
+impl TicketLevel {
+    // 見積もりポイント
+    pub fn points(&self) -> u32 {
+        match self {
+            TicketLevel::One => 1,
+            TicketLevel::Two => 2,
+            TicketLevel::Three => 3,
+            TicketLevel::Five => 5,
+            TicketLevel::Eight => 8,
+            TicketLevel::Thirteen => 13,
+            TicketLevel::TwentyOne => 21,
+            TicketLevel::Unestimated => 0,
+            TicketLevel::Custom { points, .. } => *points,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            TicketLevel::One => "One",
+            TicketLevel::Two => "Two",
+            TicketLevel::Three => "Three",
+            TicketLevel::Five => "Five",
+            TicketLevel::Eight => "Eight",
+            TicketLevel::Thirteen => "Thirteen",
+            TicketLevel::TwentyOne => "TwentyOne",
+            TicketLevel::Unestimated => "?",
+            TicketLevel::Custom { label, .. } => label,
+        }
+    }
+
+    // ラベルとポイントから見積もりを作る。組み込みと同じ組み合わせは組み込みの見積もりになる
+    pub fn with_points(label: &str, points: u32) -> Self {
+        let level = TicketLevel::from(label.to_string());
+        if level.points() == points {
+            level
+        } else {
+            TicketLevel::Custom {
+                label: label.to_string(),
+                points,
+            }
+        }
+    }
+}
+
+// 組み込み以外のラベルは数値として解釈できればそれをポイントとする
 impl From<String> for TicketLevel {
     fn from(s: String) -> Self {
         match s.as_str() {
@@ -20,20 +75,19 @@ impl From<String> for TicketLevel {
             "Five" => TicketLevel::Five,
             "Eight" => TicketLevel::Eight,
             "Thirteen" => TicketLevel::Thirteen,
-            _ => TicketLevel::One,
+            "TwentyOne" => TicketLevel::TwentyOne,
+            "?" => TicketLevel::Unestimated,
+            "" => TicketLevel::One,
+            _ => TicketLevel::Custom {
+                points: s.parse().unwrap_or(0),
+                label: s,
+            },
         }
     }
 }
 
 impl From<TicketLevel> for String {
     fn from(level: TicketLevel) -> Self {
-        match level {
-            TicketLevel::One => "One".to_string(),
-            TicketLevel::Two => "Two".to_string(),
-            TicketLevel::Three => "Three".to_string(),
-            TicketLevel::Five => "Five".to_string(),
-            TicketLevel::Eight => "Eight".to_string(),
-            TicketLevel::Thirteen => "Thirteen".to_string(),
-        }
+        level.label().to_string()
     }
 }
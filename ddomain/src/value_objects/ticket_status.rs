@@ -1,32 +1,75 @@
+use crate::value_objects::status_category::StatusCategory;
 use serde::{Deserialize, Serialize};
 
+// ファイルにはステータス名のみを保存し、独自のステータスの分類は読み込み時にワークフローから補う
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(from = "String", into = "String")]
 pub enum TicketStatus {
     #[default]
     Pending,
     Wip,
     Resolved,
     Canceled,
+    // ファイルヘッダーで宣言された独自のステータス
+    Custom {
+        name: String,
+        category: StatusCategory,
+    },
 }
+
+impl TicketStatus {
+    pub fn category(&self) -> StatusCategory {
+        match self {
+            TicketStatus::Pending => StatusCategory::Todo,
+            TicketStatus::Wip => StatusCategory::InProgress,
+            TicketStatus::Resolved => StatusCategory::Done,
+            TicketStatus::Canceled => StatusCategory::Canceled,
+            TicketStatus::Custom { category, .. } => *category,
+        }
+    }
+
+    // 名前と分類からステータスを作る。組み込みと同じ組み合わせは組み込みのステータスになる
+    pub fn with_category(name: &str, category: StatusCategory) -> Self {
+        let status = TicketStatus::from(name.to_string());
+        if status.category() == category {
+            status
+        } else {
+            TicketStatus::Custom {
+                name: name.to_string(),
+                category,
+            }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            TicketStatus::Pending => "Pending",
+            TicketStatus::Wip => "Wip",
+            TicketStatus::Resolved => "Resolved",
+            TicketStatus::Canceled => "Canceled",
+            TicketStatus::Custom { name, .. } => name,
+        }
+    }
+}
+
+// 組み込み以外の名前は分類が未確定の独自のステータスとして読み込む
 impl From<String> for TicketStatus {
     fn from(s: String) -> Self {
         match s.as_str() {
-            "Pending" => TicketStatus::Pending,
+            "" | "Pending" => TicketStatus::Pending,
             "Wip" => TicketStatus::Wip,
             "Resolved" => TicketStatus::Resolved,
             "Canceled" => TicketStatus::Canceled,
-            _ => TicketStatus::Pending,
+            _ => TicketStatus::Custom {
+                name: s,
+                category: StatusCategory::Todo,
+            },
         }
     }
 }
 
 impl From<TicketStatus> for String {
     fn from(status: TicketStatus) -> Self {
-        match status {
-            TicketStatus::Pending => "Pending".to_string(),
-            TicketStatus::Wip => "Wip".to_string(),
-            TicketStatus::Resolved => "Resolved".to_string(),
-            TicketStatus::Canceled => "Canceled".to_string(),
-        }
+        status.name().to_string()
     }
 }
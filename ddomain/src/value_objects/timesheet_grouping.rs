@@ -0,0 +1,28 @@
+// タイムシートの集計単位
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum TimesheetGrouping {
+    #[default]
+    Ticket,
+    Person,
+    Day,
+}
+
+impl From<String> for TimesheetGrouping {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "person" => TimesheetGrouping::Person,
+            "day" => TimesheetGrouping::Day,
+            _ => TimesheetGrouping::Ticket,
+        }
+    }
+}
+
+impl From<TimesheetGrouping> for String {
+    fn from(grouping: TimesheetGrouping) -> Self {
+        match grouping {
+            TimesheetGrouping::Ticket => "ticket".to_string(),
+            TimesheetGrouping::Person => "person".to_string(),
+            TimesheetGrouping::Day => "day".to_string(),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use color_eyre::Result;
+use dapplication::dtos::code_marker_dto::CodeMarkerDTO;
+use dapplication::output_ports::code_scanner::CodeScanner;
+use ddomain::services::code_markers::find_marker;
+use ddomain::value_objects::source_location::SourceLocation;
+use ignore::WalkBuilder;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+// ディレクトリ以下のソースコードを走査する。.gitignore などで除外したファイルと隠しファイルは読まない
+pub struct SourceTreeScanner {
+    root: String,
+    // 記録するパスの基準のディレクトリ
+    base: String,
+}
+
+// 区切りを / にそろえ、先頭の ./ を取り除く
+fn normalize(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    match path.strip_prefix("./") {
+        Some(rest) if !rest.is_empty() => rest.to_string(),
+        _ => path,
+    }
+}
+
+// base から見た path。どちらかが存在しない場合は None
+fn relative(path: &Path, base: &Path) -> Option<PathBuf> {
+    let (path, base) = (path.canonicalize().ok()?, base.canonicalize().ok()?);
+    let (path, base): (Vec<Component>, Vec<Component>) =
+        (path.components().collect(), base.components().collect());
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    relative.extend(&path[common..]);
+    Some(relative)
+}
+
+impl SourceTreeScanner {
+    pub fn new(root: String) -> Self {
+        Self {
+            root,
+            base: ".".to_string(),
+        }
+    }
+
+    // パスをチケットファイルのディレクトリからの相対パスで記録する。
+    // 作業ディレクトリによって同じファイルが別のパスにならないようにする
+    pub fn relative_to(mut self, base: String) -> Self {
+        self.base = base;
+        self
+    }
+
+    fn location_of(&self, path: &Path) -> String {
+        relative(path, Path::new(&self.base))
+            .map_or_else(|| normalize(path), |path| normalize(&path))
+    }
+}
+
+impl CodeScanner for SourceTreeScanner {
+    fn scan(&self) -> Result<Vec<CodeMarkerDTO>> {
+        let mut files = Vec::new();
+        // git の管理下に無いディレクトリでも .gitignore に従う
+        for entry in WalkBuilder::new(&self.root).require_git(false).build() {
+            let entry = entry?;
+            if entry.file_type().is_some_and(|kind| kind.is_file()) {
+                files.push(entry.into_path());
+            }
+        }
+        files.sort();
+
+        let mut found = Vec::new();
+        for file in files {
+            // バイナリなど UTF-8 として読めないファイルは飛ばす
+            let Ok(content) = fs::read_to_string(&file) else {
+                continue;
+            };
+            let path = self.location_of(&file);
+            for (index, line) in content.lines().enumerate() {
+                if let Some(marker) = find_marker(line) {
+                    found.push(CodeMarkerDTO {
+                        location: SourceLocation {
+                            path: path.clone(),
+                            line: index + 1,
+                        },
+                        marker,
+                    });
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    fn covers(&self, path: &str) -> bool {
+        let root = self.location_of(Path::new(&self.root));
+        let root = root.trim_end_matches('/');
+        root.is_empty() || root == "." || path == root || path.starts_with(&format!("{}/", root))
+    }
+}
@@ -0,0 +1,21 @@
+pub mod document_store;
+pub mod sqlite_store;
+mod toml_layout;
+
+use crate::migrations;
+use crate::TicketCollection;
+use ddomain::domain_errors::DomainError;
+use serde_json::Value;
+
+// チケットの集まりを丸ごと読み書きする保存先
+pub trait CollectionStore: Send + Sync {
+    fn exists(&self) -> bool;
+    // 版の移行前の内容
+    fn load_document(&self) -> Result<Value, DomainError>;
+    fn store(&self, collection: &TicketCollection) -> Result<(), DomainError>;
+
+    // 古い版のファイルは現在の版に移行してから読み込む
+    fn load(&self) -> Result<TicketCollection, DomainError> {
+        migrations::collection_from_document(self.load_document()?)
+    }
+}
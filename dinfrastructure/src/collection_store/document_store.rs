@@ -0,0 +1,84 @@
+use super::{toml_layout, CollectionStore};
+use crate::TicketCollection;
+use ddomain::domain_errors::DomainError;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl DocumentFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            DocumentFormat::Toml => "TOML",
+            DocumentFormat::Json => "JSON",
+            DocumentFormat::Yaml => "YAML",
+        }
+    }
+}
+
+// 1つのテキストファイルに全体を書き出す保存先
+pub struct DocumentStore {
+    file_path: String,
+    format: DocumentFormat,
+}
+
+impl DocumentStore {
+    pub fn new(file_path: String, format: DocumentFormat) -> Self {
+        Self { file_path, format }
+    }
+
+    fn parse_error(&self, error: impl ToString) -> DomainError {
+        DomainError::DocumentParse(self.format.name().to_string(), error.to_string())
+    }
+
+    fn serialize_error(&self, error: impl ToString) -> DomainError {
+        DomainError::DocumentSerialize(self.format.name().to_string(), error.to_string())
+    }
+}
+
+impl CollectionStore for DocumentStore {
+    fn exists(&self) -> bool {
+        Path::new(&self.file_path).exists()
+    }
+
+    fn load_document(&self) -> Result<Value, DomainError> {
+        let file_str = fs::read_to_string(&self.file_path).map_err(DomainError::FileRead)?;
+        if file_str.trim().is_empty() {
+            return Err(DomainError::EmptyFile);
+        }
+        match self.format {
+            DocumentFormat::Toml => toml::from_str(&file_str).map_err(DomainError::TomlParse),
+            DocumentFormat::Json => {
+                serde_json::from_str(&file_str).map_err(|e| self.parse_error(e))
+            }
+            DocumentFormat::Yaml => {
+                serde_yaml::from_str(&file_str).map_err(|e| self.parse_error(e))
+            }
+        }
+    }
+
+    fn store(&self, collection: &TicketCollection) -> Result<(), DomainError> {
+        let content = match self.format {
+            DocumentFormat::Toml => {
+                let fresh = toml::to_string(collection)?;
+                fs::read_to_string(&self.file_path)
+                    .ok()
+                    .and_then(|existing| toml_layout::update_document(&existing, &fresh))
+                    .unwrap_or(fresh)
+            }
+            DocumentFormat::Json => serde_json::to_string_pretty(collection)
+                .map(|json| json + "\n")
+                .map_err(|e| self.serialize_error(e))?,
+            DocumentFormat::Yaml => {
+                serde_yaml::to_string(collection).map_err(|e| self.serialize_error(e))?
+            }
+        };
+        fs::write(&self.file_path, content).map_err(DomainError::FileWrite)
+    }
+}
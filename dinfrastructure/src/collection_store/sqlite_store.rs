@@ -0,0 +1,130 @@
+use super::CollectionStore;
+use crate::TicketCollection;
+use ddomain::domain_errors::DomainError;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{json, Value};
+use std::path::Path;
+
+// 大きなバックログ向けの SQLite の保存先。
+// 1行1チケットで、検索用の列とは別に全項目を JSON で持つので他の形式と相互に変換しても情報が欠けない
+pub struct SqliteStore {
+    file_path: String,
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS header (id INTEGER PRIMARY KEY CHECK (id = 1), data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS sprints (position INTEGER PRIMARY KEY, name TEXT NOT NULL, data TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS tickets (
+    position INTEGER PRIMARY KEY,
+    id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    assignee TEXT,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS tickets_id ON tickets (id);
+";
+
+fn database_error(error: impl ToString) -> DomainError {
+    DomainError::Database(error.to_string())
+}
+
+impl SqliteStore {
+    pub fn new(file_path: String) -> Self {
+        Self { file_path }
+    }
+
+    fn open(&self) -> Result<Connection, DomainError> {
+        Connection::open(&self.file_path).map_err(database_error)
+    }
+}
+
+// 保存済みの行の JSON を読み込む
+fn rows<T>(connection: &Connection, sql: &str) -> Result<Vec<T>, DomainError>
+where
+    T: for<'a> serde::Deserialize<'a>,
+{
+    let mut statement = connection.prepare(sql).map_err(database_error)?;
+    let data = statement
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(database_error)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(database_error)?;
+    data.iter()
+        .map(|json| {
+            serde_json::from_str(json)
+                .map_err(|e| DomainError::DocumentParse("SQLite".to_string(), e.to_string()))
+        })
+        .collect()
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, DomainError> {
+    serde_json::to_string(value)
+        .map_err(|e| DomainError::DocumentSerialize("SQLite".to_string(), e.to_string()))
+}
+
+impl CollectionStore for SqliteStore {
+    fn exists(&self) -> bool {
+        Path::new(&self.file_path).exists()
+    }
+
+    fn load_document(&self) -> Result<Value, DomainError> {
+        let connection = self.open()?;
+        let has_tickets: Option<String> = connection
+            .query_row(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'tickets'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(database_error)?;
+        if has_tickets.is_none() {
+            return Err(DomainError::EmptyFile);
+        }
+
+        let header: Option<Value> = rows(&connection, "SELECT data FROM header")?.pop();
+        Ok(json!({
+            "header": header.unwrap_or_else(|| json!({})),
+            "sprint_data": rows::<Value>(&connection, "SELECT data FROM sprints ORDER BY position")?,
+            "ticket_data": rows::<Value>(&connection, "SELECT data FROM tickets ORDER BY position")?,
+        }))
+    }
+
+    // 1つのトランザクションで全体を置き換える
+    fn store(&self, collection: &TicketCollection) -> Result<(), DomainError> {
+        let mut connection = self.open()?;
+        let transaction = connection.transaction().map_err(database_error)?;
+        transaction.execute_batch(SCHEMA).map_err(database_error)?;
+        transaction
+            .execute_batch("DELETE FROM header; DELETE FROM sprints; DELETE FROM tickets;")
+            .map_err(database_error)?;
+        transaction
+            .execute(
+                "INSERT INTO header (id, data) VALUES (1, ?1)",
+                params![to_json(&collection.header)?],
+            )
+            .map_err(database_error)?;
+        for (position, sprint) in collection.sprint_data.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO sprints (position, name, data) VALUES (?1, ?2, ?3)",
+                    params![position as i64, sprint.name, to_json(sprint)?],
+                )
+                .map_err(database_error)?;
+        }
+        for (position, ticket) in collection.ticket_data.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO tickets (position, id, status, assignee, data) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        position as i64,
+                        ticket.id,
+                        ticket.status.name(),
+                        ticket.assignee,
+                        to_json(ticket)?
+                    ],
+                )
+                .map_err(database_error)?;
+        }
+        transaction.commit().map_err(database_error)
+    }
+}
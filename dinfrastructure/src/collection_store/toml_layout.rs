@@ -0,0 +1,224 @@
+use crate::TicketCollection;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint, ticket::Ticket};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
+
+// 手で整えたファイルの並びとコメントを残したまま、内容が変わった部分だけを書き換える。
+// 既存のファイルが読めない場合や、書き換えた結果が保存する内容と一致しない場合は None
+pub fn update_document(existing: &str, content: &str) -> Option<String> {
+    let mut document: DocumentMut = existing.parse().ok()?;
+    let fresh: DocumentMut = content.parse().ok()?;
+    let root = document.as_table_mut();
+    let is_new_header = !root.contains_key("header");
+
+    for (key, fresh_item) in fresh.iter() {
+        match (key, root.get_mut(key), fresh_item) {
+            ("header", Some(Item::Table(table)), Item::Table(fresh_table)) => {
+                merge_entity::<FileHeader>(table, fresh_table)
+            }
+            ("sprint_data", Some(Item::ArrayOfTables(array)), Item::ArrayOfTables(fresh_array)) => {
+                merge_by_position::<Sprint>(array, fresh_array)
+            }
+            ("ticket_data", Some(Item::ArrayOfTables(array)), Item::ArrayOfTables(fresh_array)) => {
+                merge_by_id(array, fresh_array)
+            }
+            (_, Some(item), _) if same(item, fresh_item) => {}
+            _ => {
+                root.insert(key, fresh_item.clone());
+            }
+        }
+    }
+    let removed: Vec<String> = root
+        .iter()
+        .filter(|(key, _)| !fresh.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in removed {
+        root.remove(&key);
+    }
+
+    // 後から加わったヘッダーはファイルの先頭に置く
+    if is_new_header {
+        root.sort_values_by(|a, _, b, _| (a.get() != "header").cmp(&(b.get() != "header")));
+    }
+    renumber(root, &mut 0);
+
+    let updated = document.to_string();
+    let expected = serde_json::to_value(toml::from_str::<TicketCollection>(content).ok()?).ok()?;
+    let actual = serde_json::to_value(toml::from_str::<TicketCollection>(&updated).ok()?).ok()?;
+    (actual == expected).then_some(updated)
+}
+
+// 表の出力順は文書内の位置で決まるため、並び替えや追加の後の順序に振り直す
+fn renumber(table: &mut Table, next: &mut usize) {
+    if !table.is_dotted() {
+        table.set_position(*next);
+        *next += 1;
+    }
+    for (_, item) in table.iter_mut() {
+        match item {
+            Item::Table(table) => renumber(table, next),
+            Item::ArrayOfTables(array) => {
+                for table in array.iter_mut() {
+                    renumber(table, next);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// 書式を無視した値(文字列の引用符や配列の改行の違いは同じとみなす)
+fn plain(item: &Item) -> Option<toml::Value> {
+    let mut document = DocumentMut::new();
+    document.insert("v", item.clone());
+    toml::from_str::<toml::Table>(&document.to_string())
+        .ok()?
+        .remove("v")
+}
+
+fn same(a: &Item, b: &Item) -> bool {
+    plain(a).is_some_and(|value| Some(value) == plain(b))
+}
+
+// 表が表す内容。省略された項目は既定値として扱うので、既定値を書いた行と省略した行は同じになる
+fn meaning<T: DeserializeOwned + Serialize>(table: &Table) -> Option<serde_json::Value> {
+    let mut document = DocumentMut::new();
+    *document.as_table_mut() = table.clone();
+    let entity: T = toml::from_str(&document.to_string()).ok()?;
+    serde_json::to_value(entity).ok()
+}
+
+// ヘッダー・スプリント・チケットの1件。内容が同じなら手を付けず、
+// 変わった場合も変わった項目だけを書き換える
+fn merge_entity<T: DeserializeOwned + Serialize>(table: &mut Table, fresh: &Table) {
+    let target = meaning::<T>(fresh);
+    if target.is_some() && meaning::<T>(table) == target {
+        return;
+    }
+
+    for (key, fresh_item) in fresh.iter() {
+        // 置き換えても内容が変わらない項目(既定値だけの違いなど)はそのままにする
+        let mut replaced = table.clone();
+        replaced.insert(key, fresh_item.clone());
+        if meaning::<T>(&replaced) == meaning::<T>(table) {
+            continue;
+        }
+        match table.get_mut(key) {
+            Some(item) => merge_item(item, fresh_item),
+            None => {
+                table.insert(key, fresh_item.clone());
+            }
+        }
+    }
+    // 新しい内容に無い項目は、既定値が書かれているだけなら残す
+    let absent: Vec<String> = table
+        .iter()
+        .filter(|(key, _)| !fresh.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in absent {
+        let mut without = table.clone();
+        without.remove(&key);
+        if meaning::<T>(&without) != meaning::<T>(table) {
+            *table = without;
+        }
+    }
+
+    // 書き換えても一致しない場合は新しい内容で置き換える(前に付いたコメントは残す)
+    if meaning::<T>(table) != target {
+        let decor = table.decor().clone();
+        *table = fresh.clone();
+        *table.decor_mut() = decor;
+    }
+}
+
+fn merge_item(item: &mut Item, fresh: &Item) {
+    if same(item, fresh) {
+        return;
+    }
+    match (item, fresh) {
+        (Item::Table(table), Item::Table(fresh_table)) => merge_table(table, fresh_table),
+        (Item::ArrayOfTables(array), Item::ArrayOfTables(fresh_array)) => {
+            while array.len() > fresh_array.len() {
+                array.remove(array.len() - 1);
+            }
+            for (index, fresh_table) in fresh_array.iter().enumerate() {
+                match array.get_mut(index) {
+                    Some(table) => merge_table(table, fresh_table),
+                    None => array.push(fresh_table.clone()),
+                }
+            }
+        }
+        // 値だけを差し替え、行末のコメントなどの装飾は残す
+        (item, fresh) => match (item.as_value_mut(), fresh.as_value()) {
+            (Some(value), Some(new_value)) => {
+                let decor = value.decor().clone();
+                *value = new_value.clone();
+                *value.decor_mut() = decor;
+            }
+            (_, _) => *item = fresh.clone(),
+        },
+    }
+}
+
+// コメントやタグの定義など、チケットやヘッダーの中の表
+fn merge_table(table: &mut Table, fresh: &Table) {
+    let removed: Vec<String> = table
+        .iter()
+        .filter(|(key, _)| !fresh.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in removed {
+        table.remove(&key);
+    }
+    for (key, fresh_item) in fresh.iter() {
+        match table.get_mut(key) {
+            Some(item) => merge_item(item, fresh_item),
+            None => {
+                table.insert(key, fresh_item.clone());
+            }
+        }
+    }
+}
+
+fn merge_by_position<T: DeserializeOwned + Serialize>(
+    array: &mut ArrayOfTables,
+    fresh: &ArrayOfTables,
+) {
+    while array.len() > fresh.len() {
+        array.remove(array.len() - 1);
+    }
+    for (index, fresh_table) in fresh.iter().enumerate() {
+        match array.get_mut(index) {
+            Some(table) => merge_entity::<T>(table, fresh_table),
+            None => array.push(fresh_table.clone()),
+        }
+    }
+}
+
+fn id_of(table: &Table) -> Option<&str> {
+    table.get("id").and_then(Item::as_str)
+}
+
+// チケットは並び替えや削除があっても ID で対応付ける
+fn merge_by_id(array: &mut ArrayOfTables, fresh: &ArrayOfTables) {
+    let mut existing: Vec<Option<Table>> = array.iter().cloned().map(Some).collect();
+    let mut merged = ArrayOfTables::new();
+    for fresh_table in fresh.iter() {
+        let found = existing.iter_mut().find(|table| {
+            table
+                .as_ref()
+                .is_some_and(|table| id_of(table).is_some() && id_of(table) == id_of(fresh_table))
+        });
+        match found.and_then(Option::take) {
+            Some(mut table) => {
+                merge_entity::<Ticket>(&mut table, fresh_table);
+                merged.push(table);
+            }
+            None => merged.push(fresh_table.clone()),
+        }
+    }
+    *array = merged;
+}
@@ -0,0 +1,19 @@
+pub mod csv_exporter;
+pub mod html_exporter;
+pub mod markdown_exporter;
+
+use csv_exporter::CsvExporter;
+use dapplication::dtos::export_dto::ExportFormat;
+use dapplication::output_ports::ticket_exporter::TicketExporter;
+use html_exporter::HtmlExporter;
+use markdown_exporter::MarkdownExporter;
+
+// title は HTML のページの見出しに使う
+pub fn exporter_for(format: ExportFormat, title: &str) -> Box<dyn TicketExporter> {
+    match format {
+        ExportFormat::Markdown => Box::new(MarkdownExporter::Table),
+        ExportFormat::Checklist => Box::new(MarkdownExporter::Checklist),
+        ExportFormat::Html => Box::new(HtmlExporter::new(title.to_string())),
+        ExportFormat::Csv => Box::new(CsvExporter),
+    }
+}
@@ -0,0 +1,77 @@
+use color_eyre::Result;
+use dapplication::dtos::export_dto::ExportDTO;
+use dapplication::output_ports::ticket_exporter::TicketExporter;
+
+// 1行1チケット。カスタムフィールドは登場した順に列として末尾へ追加する
+pub struct CsvExporter;
+
+const COLUMNS: [&str; 16] = [
+    "id",
+    "external_id",
+    "title",
+    "status",
+    "category",
+    "priority",
+    "estimate",
+    "assignee",
+    "sprint",
+    "parent",
+    "blocked_by",
+    "tags",
+    "due",
+    "recurrence",
+    "created_at",
+    "resolved_at",
+];
+
+impl TicketExporter for CsvExporter {
+    fn export(&self, export: &ExportDTO) -> Result<String> {
+        let mut field_names: Vec<&str> = Vec::new();
+        for field in export.tickets.iter().flat_map(|ticket| &ticket.fields) {
+            if !field.value.is_empty() && !field_names.contains(&field.name.as_str()) {
+                field_names.push(&field.name);
+            }
+        }
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(COLUMNS.iter().chain(field_names.iter()))?;
+        for ticket in &export.tickets {
+            let mut record = vec![
+                ticket.id.clone(),
+                ticket.external_id.clone(),
+                ticket.title.clone(),
+                ticket.status.clone(),
+                ticket.status_category.clone(),
+                ticket.priority.clone(),
+                ticket.level.clone(),
+                ticket.assignee.clone(),
+                ticket.sprint.clone(),
+                ticket.parent.clone(),
+                ticket.blocked_by.join(" "),
+                ticket
+                    .tags
+                    .iter()
+                    .map(|tag| tag.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                ticket.due.clone(),
+                ticket.recurrence.clone(),
+                ticket.created_at.to_rfc3339(),
+                ticket
+                    .resolved_at
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default(),
+            ];
+            record.extend(field_names.iter().map(|name| {
+                ticket
+                    .fields
+                    .iter()
+                    .find(|field| &field.name == name)
+                    .map(|field| field.value.clone())
+                    .unwrap_or_default()
+            }));
+            writer.write_record(&record)?;
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
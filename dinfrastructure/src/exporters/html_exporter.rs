@@ -0,0 +1,129 @@
+use color_eyre::Result;
+use dapplication::dtos::export_dto::ExportDTO;
+use dapplication::dtos::ticket_dto::TicketDTO;
+use dapplication::output_ports::ticket_exporter::TicketExporter;
+
+// 外部のファイルに依存しない1枚のHTMLページ。ステータスごとに表を分ける
+pub struct HtmlExporter {
+    title: String,
+}
+
+impl HtmlExporter {
+    pub fn new(title: String) -> Self {
+        Self { title }
+    }
+}
+
+const STYLE: &str = "\
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2rem; color: #24292f; }
+h1 { margin-bottom: 0.2rem; }
+.generated { color: #6e7781; margin-top: 0; }
+section { margin-top: 2rem; }
+h2 { border-left: 6px solid #8c959f; padding-left: 0.5rem; }
+h2 .count { color: #6e7781; font-weight: normal; font-size: 0.8em; }
+.todo h2 { border-color: #8c959f; }
+.in-progress h2 { border-color: #0969da; }
+.done h2 { border-color: #1a7f37; }
+.canceled h2 { border-color: #d0d7de; }
+.canceled td { color: #8c959f; text-decoration: line-through; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border-bottom: 1px solid #d0d7de; padding: 0.4rem 0.6rem; text-align: left; }
+th { background: #f6f8fa; }
+.tag { display: inline-block; border-radius: 1em; padding: 0 0.5em; margin-right: 0.2em; background: #eaeef2; font-size: 0.85em; }
+.priority-critical { color: #cf222e; font-weight: bold; }
+.priority-high { color: #bc4c00; }
+.priority-low { color: #6e7781; }
+.overdue { color: #cf222e; }
+";
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn row(ticket: &TicketDTO) -> String {
+    let tags: String = ticket
+        .tags
+        .iter()
+        .map(|tag| {
+            let style = if tag.color.is_empty() {
+                String::new()
+            } else {
+                format!(" style=\"border: 1px solid {}\"", escape(&tag.color))
+            };
+            format!("<span class=\"tag\"{}>{}</span>", style, escape(&tag.name))
+        })
+        .collect();
+    let due_class = if ticket.due_bucket == "Overdue" {
+        " class=\"overdue\""
+    } else {
+        ""
+    };
+    format!(
+        "<tr><td>{}</td><td>{}</td><td class=\"priority-{}\">{}</td><td>{}</td><td>{}</td><td{}>{}</td><td>{}</td></tr>\n",
+        escape(&ticket.id),
+        escape(&ticket.title),
+        escape(&ticket.priority.to_lowercase()),
+        escape(&ticket.priority),
+        escape(&ticket.level),
+        escape(&ticket.assignee),
+        due_class,
+        escape(&ticket.due),
+        tags,
+    )
+}
+
+impl TicketExporter for HtmlExporter {
+    fn export(&self, export: &ExportDTO) -> Result<String> {
+        // 宣言されたステータスの順に並べ、宣言されていないステータスは末尾に追加する
+        let mut statuses: Vec<&str> = export.statuses.iter().map(String::as_str).collect();
+        for ticket in &export.tickets {
+            if !statuses.contains(&ticket.status.as_str()) {
+                statuses.push(&ticket.status);
+            }
+        }
+
+        let mut sections = String::new();
+        for status in statuses {
+            let tickets: Vec<&TicketDTO> = export
+                .tickets
+                .iter()
+                .filter(|ticket| ticket.status == status)
+                .collect();
+            let Some(first) = tickets.first() else {
+                continue;
+            };
+            sections.push_str(&format!(
+                "<section class=\"{}\">\n<h2>{} <span class=\"count\">({})</span></h2>\n\
+                 <table>\n<thead><tr><th>ID</th><th>Title</th><th>Priority</th><th>Estimate</th>\
+                 <th>Assignee</th><th>Due</th><th>Tags</th></tr></thead>\n<tbody>\n",
+                escape(&first.status_category),
+                escape(status),
+                tickets.len()
+            ));
+            for ticket in tickets {
+                sections.push_str(&row(ticket));
+            }
+            sections.push_str("</tbody>\n</table>\n</section>\n");
+        }
+
+        Ok(format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\n{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+             <p class=\"generated\">{count} tickets, generated at {generated}</p>\n{sections}</body>\n</html>\n",
+            title = escape(&self.title),
+            count = export.tickets.len(),
+            generated = export.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        ))
+    }
+}
@@ -0,0 +1,81 @@
+use color_eyre::Result;
+use dapplication::dtos::export_dto::ExportDTO;
+use dapplication::dtos::ticket_dto::TicketDTO;
+use dapplication::output_ports::ticket_exporter::TicketExporter;
+
+pub enum MarkdownExporter {
+    Table,
+    // 完了したチケットにチェックを付けたタスクリスト
+    Checklist,
+}
+
+// 表のセルを壊さないように | と改行を置き換える
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn tags(ticket: &TicketDTO) -> String {
+    ticket
+        .tags
+        .iter()
+        .map(|tag| format!("#{}", tag.name))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn table(tickets: &[TicketDTO]) -> String {
+    let mut out = String::from(
+        "| ID | Title | Status | Priority | Estimate | Assignee | Due | Tags |\n\
+         |----|-------|--------|----------|----------|----------|-----|------|\n",
+    );
+    for ticket in tickets {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            escape(&ticket.id),
+            escape(&ticket.title),
+            escape(&ticket.status),
+            ticket.priority,
+            escape(&ticket.level),
+            escape(&ticket.assignee),
+            ticket.due,
+            escape(&tags(ticket)),
+        ));
+    }
+    out
+}
+
+fn checklist(tickets: &[TicketDTO]) -> String {
+    let mut out = String::new();
+    for ticket in tickets {
+        let checked = matches!(ticket.status_category.as_str(), "done" | "canceled");
+        let mut line = format!("{} {}", ticket.id, ticket.title.replace(['\r', '\n'], " "));
+        // 中止したチケットは打ち消し線で区別する
+        if ticket.status_category == "canceled" {
+            line = format!("~~{}~~", line);
+        }
+        let details: Vec<String> = [
+            Some(ticket.status.clone()),
+            (!ticket.assignee.is_empty()).then(|| format!("@{}", ticket.assignee)),
+            (!ticket.due.is_empty()).then(|| format!("due {}", ticket.due)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        out.push_str(&format!(
+            "- [{}] {} ({})\n",
+            if checked { "x" } else { " " },
+            line,
+            details.join(", ")
+        ));
+    }
+    out
+}
+
+impl TicketExporter for MarkdownExporter {
+    fn export(&self, export: &ExportDTO) -> Result<String> {
+        Ok(match self {
+            MarkdownExporter::Table => table(&export.tickets),
+            MarkdownExporter::Checklist => checklist(&export.tickets),
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use dapplication::dtos::commit_dto::CommitDTO;
+use dapplication::output_ports::version_control::VersionControl;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// 履歴の項目とコミットの区切り(メッセージに含まれない制御文字)
+const FIELD_SEPARATOR: char = '\u{1f}';
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+// git コマンドでローカルのリポジトリを操作する
+pub struct GitCli {
+    work_dir: PathBuf,
+}
+
+impl GitCli {
+    // ファイルを含む作業ツリー。git が無いか管理下に無い場合は None
+    pub fn discover(file_path: &str) -> Option<Self> {
+        let dir = Path::new(file_path)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let top = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        Some(Self {
+            work_dir: PathBuf::from(top),
+        })
+    }
+
+    fn git(&self, args: &[&str]) -> Result<Output> {
+        Ok(Command::new("git")
+            .arg("-C")
+            .arg(&self.work_dir)
+            .args(args)
+            .output()?)
+    }
+}
+
+fn failure(command: &str, output: &Output) -> color_eyre::Report {
+    eyre!(
+        "git {} failed: {}",
+        command,
+        String::from_utf8_lossy(&output.stderr).trim()
+    )
+}
+
+fn parse_commit(record: &str) -> Option<CommitDTO> {
+    let mut fields = record.trim_start_matches('\n').splitn(4, FIELD_SEPARATOR);
+    let hash = fields.next()?.to_string();
+    let author = fields.next()?.to_string();
+    let date = DateTime::parse_from_rfc3339(fields.next()?)
+        .ok()?
+        .with_timezone(&Utc);
+    let message = fields.next()?.trim_end().to_string();
+    (!hash.is_empty()).then_some(CommitDTO {
+        hash,
+        author,
+        date,
+        message,
+        closes: false,
+    })
+}
+
+impl VersionControl for GitCli {
+    fn commits(&self) -> Result<Vec<CommitDTO>> {
+        // まだコミットが無いリポジトリでは履歴は空
+        if !self
+            .git(&["rev-parse", "--verify", "--quiet", "HEAD"])?
+            .status
+            .success()
+        {
+            return Ok(Vec::new());
+        }
+        let format = format!(
+            "--format=%H{0}%an{0}%aI{0}%B{1}",
+            FIELD_SEPARATOR, RECORD_SEPARATOR
+        );
+        let output = self.git(&["log", "--no-color", &format])?;
+        if !output.status.success() {
+            return Err(failure("log", &output));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split(RECORD_SEPARATOR)
+            .filter_map(parse_commit)
+            .collect())
+    }
+
+    fn create_branch(&self, name: &str) -> Result<()> {
+        let output = self.git(&["checkout", "-b", name])?;
+        if !output.status.success() {
+            return Err(failure("checkout", &output));
+        }
+        Ok(())
+    }
+}
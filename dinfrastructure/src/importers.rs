@@ -0,0 +1,127 @@
+pub mod csv_importer;
+pub mod github_importer;
+pub mod jira_importer;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use color_eyre::Result;
+use csv::StringRecord;
+use csv_importer::CsvImporter;
+use dapplication::dtos::import_dto::ImportFormat;
+use dapplication::output_ports::ticket_importer::TicketImporter;
+use github_importer::GitHubImporter;
+use jira_importer::JiraImporter;
+
+// mapping は CSV の列の対応("title=Summary" など)。CSV 以外では使わない
+pub fn importer_for(
+    format: ImportFormat,
+    path: String,
+    mapping: &[String],
+) -> Result<Box<dyn TicketImporter>> {
+    Ok(match format {
+        ImportFormat::Csv => Box::new(CsvImporter::new(path, mapping)?),
+        ImportFormat::GitHub => Box::new(GitHubImporter::new(path)),
+        ImportFormat::Jira => Box::new(JiraImporter::new(path)),
+    })
+}
+
+// 分類の無い取り込み元のステータス名から分類を推測する
+fn guess_category(status: &str) -> String {
+    let status = status.to_lowercase();
+    let category = if [
+        "cancel",
+        "won't",
+        "wont",
+        "not planned",
+        "not_planned",
+        "duplicate",
+        "reject",
+        "declined",
+    ]
+    .iter()
+    .any(|word| status.contains(word))
+    {
+        "canceled"
+    } else if [
+        "done", "closed", "resolved", "complete", "fixed", "finished",
+    ]
+    .iter()
+    .any(|word| status.contains(word))
+    {
+        "done"
+    } else if ["progress", "wip", "doing", "review", "started", "active"]
+        .iter()
+        .any(|word| status.contains(word))
+    {
+        "in-progress"
+    } else {
+        "todo"
+    };
+    category.to_string()
+}
+
+// 取り込み元の優先度を Critical/High/Medium/Low に揃える。不明な場合は空文字列
+fn normalize_priority(priority: &str) -> String {
+    let normalized = match priority.trim().to_lowercase().as_str() {
+        "highest" | "blocker" | "critical" | "urgent" | "p0" => "Critical",
+        "high" | "major" | "p1" => "High",
+        "medium" | "normal" | "p2" => "Medium",
+        "low" | "lowest" | "minor" | "trivial" | "p3" | "p4" => "Low",
+        _ => "",
+    };
+    normalized.to_string()
+}
+
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%d/%b/%y", "%d/%b/%Y"];
+
+// ISO 8601 と Jira の書き出し形式("18/Oct/26 3:45 PM")の日時を読む。日付のみの場合は0時とする
+fn parse_datetime(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Some(at.with_timezone(&Utc));
+    }
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%d/%b/%y %I:%M %p",
+        "%d/%b/%Y %I:%M %p",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| {
+        DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+    .map(|at| at.and_utc())
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    parse_datetime(text).map(|at| at.date_naive())
+}
+
+// 名前が一致する列(大文字小文字と BOM を無視)の位置。Jira のラベルのように同じ名前の列が複数ある場合もある
+fn column_indices(headers: &StringRecord, name: &str) -> Vec<usize> {
+    headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| {
+            header
+                .trim_start_matches('\u{feff}')
+                .trim()
+                .eq_ignore_ascii_case(name)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+// 候補の列のうち最初に値が入っているもの
+fn first_value(record: &StringRecord, indices: &[usize]) -> String {
+    indices
+        .iter()
+        .filter_map(|index| record.get(*index))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,111 @@
+use super::{
+    column_indices, first_value, guess_category, normalize_priority, parse_date, parse_datetime,
+};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use dapplication::dtos::import_dto::ImportedTicketDTO;
+use dapplication::output_ports::ticket_importer::TicketImporter;
+use std::collections::BTreeMap;
+
+// 取り込める項目と既定の列名(digger export --format csv の列名)
+const FIELDS: [(&str, &[&str]); 11] = [
+    ("id", &["external_id", "id"]),
+    ("title", &["title"]),
+    ("status", &["status"]),
+    ("category", &["category"]),
+    ("estimate", &["estimate"]),
+    ("priority", &["priority"]),
+    ("assignee", &["assignee"]),
+    ("tags", &["tags"]),
+    ("due", &["due"]),
+    ("created_at", &["created_at"]),
+    ("resolved_at", &["resolved_at"]),
+];
+
+// 任意の CSV。1行目の列名を "title=Summary" のような対応で項目に割り当てる
+pub struct CsvImporter {
+    path: String,
+    mapping: BTreeMap<String, String>,
+}
+
+impl CsvImporter {
+    pub fn new(path: String, mapping: &[String]) -> Result<Self> {
+        let mut columns = BTreeMap::new();
+        for entry in mapping.iter().flat_map(|entry| entry.split(',')) {
+            let (field, column) = entry.split_once('=').ok_or_else(|| {
+                eyre!("invalid column mapping '{}' (expected field=column)", entry)
+            })?;
+            let field = field.trim();
+            if !FIELDS.iter().any(|(name, _)| *name == field) {
+                return Err(eyre!(
+                    "unknown field '{}' in column mapping (expected one of {})",
+                    field,
+                    FIELDS.map(|(name, _)| name).join(", ")
+                ));
+            }
+            columns.insert(field.to_string(), column.trim().to_string());
+        }
+        Ok(Self {
+            path,
+            mapping: columns,
+        })
+    }
+}
+
+impl TicketImporter for CsvImporter {
+    fn read(&self) -> Result<Vec<ImportedTicketDTO>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_path(&self.path)?;
+        let headers = reader.headers()?.clone();
+        let columns: BTreeMap<&str, Vec<usize>> = FIELDS
+            .iter()
+            .map(|(field, defaults)| {
+                let indices = match self.mapping.get(*field) {
+                    Some(column) => column_indices(&headers, column),
+                    None => defaults
+                        .iter()
+                        .flat_map(|column| column_indices(&headers, column))
+                        .collect(),
+                };
+                (*field, indices)
+            })
+            .collect();
+        if columns["title"].is_empty() {
+            return Err(eyre!(
+                "no title column in {} (map one with --map title=<column>)",
+                self.path
+            ));
+        }
+
+        let mut tickets = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let value = |field: &str| first_value(&record, &columns[field]);
+            let status = value("status");
+            let category = value("category");
+            tickets.push(ImportedTicketDTO {
+                external_id: value("id"),
+                title: value("title"),
+                category: if category.is_empty() {
+                    guess_category(&status)
+                } else {
+                    category
+                },
+                status,
+                estimate: value("estimate"),
+                priority: normalize_priority(&value("priority")),
+                assignee: value("assignee"),
+                tags: value("tags")
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect(),
+                due: parse_date(&value("due")),
+                created_at: parse_datetime(&value("created_at")),
+                resolved_at: parse_datetime(&value("resolved_at")),
+            });
+        }
+        Ok(tickets)
+    }
+}
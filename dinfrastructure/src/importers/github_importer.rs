@@ -0,0 +1,143 @@
+use super::{guess_category, normalize_priority};
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use dapplication::dtos::import_dto::ImportedTicketDTO;
+use dapplication::output_ports::ticket_importer::TicketImporter;
+use serde::Deserialize;
+use std::fs;
+
+// GitHub の課題の JSON。REST API の応答と `gh issue list --json` の出力のどちらも読める
+pub struct GitHubImporter {
+    path: String,
+}
+
+impl GitHubImporter {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitHubLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default, alias = "stateReason")]
+    state_reason: Option<String>,
+    #[serde(default)]
+    labels: Vec<GitHubLabel>,
+    #[serde(default)]
+    assignees: Vec<GitHubUser>,
+    #[serde(default, alias = "createdAt")]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default, alias = "closedAt")]
+    closed_at: Option<DateTime<Utc>>,
+    // REST API ではプルリクエストも課題として返る
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+// "points: 3" や "priority/high" のような「キー: 値」形式のラベル
+fn label_value<'a>(label: &'a str, keys: &[&str]) -> Option<&'a str> {
+    let (key, value) = label.split_once([':', '/', '='])?;
+    keys.iter()
+        .any(|candidate| key.trim().eq_ignore_ascii_case(candidate))
+        .then(|| value.trim())
+}
+
+const ESTIMATE_KEYS: [&str; 5] = ["points", "point", "estimate", "size", "sp"];
+const PRIORITY_KEYS: [&str; 2] = ["priority", "prio"];
+const STATUS_KEYS: [&str; 2] = ["status", "state"];
+
+impl From<GitHubIssue> for ImportedTicketDTO {
+    fn from(issue: GitHubIssue) -> Self {
+        let labels: Vec<&str> = issue
+            .labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .collect();
+        let find = |keys: &[&str]| labels.iter().find_map(|label| label_value(label, keys));
+        // 優先度は "P1" や "critical" のようなラベルも受け付ける
+        let priority = find(&PRIORITY_KEYS)
+            .map(normalize_priority)
+            .or_else(|| {
+                labels
+                    .iter()
+                    .map(|label| normalize_priority(label))
+                    .find(|priority| !priority.is_empty())
+            })
+            .unwrap_or_default();
+        let status = find(&STATUS_KEYS).unwrap_or_default().to_string();
+
+        let category = if issue.state.eq_ignore_ascii_case("closed") {
+            match issue
+                .state_reason
+                .as_deref()
+                .map(str::to_lowercase)
+                .as_deref()
+            {
+                Some("not_planned") | Some("duplicate") => "canceled".to_string(),
+                _ => "done".to_string(),
+            }
+        } else if status.is_empty() {
+            "todo".to_string()
+        } else {
+            // 閉じていない課題はステータスのラベルから作業中かどうかを推測する
+            match guess_category(&status).as_str() {
+                "in-progress" => "in-progress".to_string(),
+                _ => "todo".to_string(),
+            }
+        };
+
+        ImportedTicketDTO {
+            external_id: format!("GH-{}", issue.number),
+            title: issue.title,
+            status,
+            category,
+            estimate: find(&ESTIMATE_KEYS).unwrap_or_default().to_string(),
+            priority,
+            assignee: issue
+                .assignees
+                .first()
+                .map(|user| user.login.clone())
+                .unwrap_or_default(),
+            tags: labels
+                .iter()
+                .filter(|label| find_key(label).is_none() && normalize_priority(label).is_empty())
+                .map(|label| label.to_string())
+                .collect(),
+            due: None,
+            created_at: issue.created_at,
+            resolved_at: issue.closed_at,
+        }
+    }
+}
+
+// 見積もり・優先度・ステータスを表すラベルはタグにしない
+fn find_key(label: &str) -> Option<&str> {
+    [&ESTIMATE_KEYS[..], &PRIORITY_KEYS[..], &STATUS_KEYS[..]]
+        .iter()
+        .find_map(|keys| label_value(label, keys))
+}
+
+impl TicketImporter for GitHubImporter {
+    fn read(&self) -> Result<Vec<ImportedTicketDTO>> {
+        let issues: Vec<GitHubIssue> = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .map(ImportedTicketDTO::from)
+            .collect())
+    }
+}
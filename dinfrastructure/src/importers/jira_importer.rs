@@ -0,0 +1,115 @@
+use super::{
+    column_indices, first_value, guess_category, normalize_priority, parse_date, parse_datetime,
+};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use csv::StringRecord;
+use dapplication::dtos::import_dto::ImportedTicketDTO;
+use dapplication::output_ports::ticket_importer::TicketImporter;
+
+// Jira の「CSV(すべてのフィールド)」の書き出し。取り込み元の ID は課題キー(PROJ-12)
+pub struct JiraImporter {
+    path: String,
+}
+
+impl JiraImporter {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
+// 完了していても作業しなかったことを表す解決状況
+const CANCELED_RESOLUTIONS: [&str; 5] = [
+    "won't do",
+    "won't fix",
+    "duplicate",
+    "cannot reproduce",
+    "declined",
+];
+
+fn category(status: &str, status_category: &str, resolution: &str) -> String {
+    let category = match status_category.to_lowercase().as_str() {
+        "to do" | "new" => "todo".to_string(),
+        "in progress" | "indeterminate" => "in-progress".to_string(),
+        "done" | "complete" => "done".to_string(),
+        _ => guess_category(status),
+    };
+    if category == "done" && CANCELED_RESOLUTIONS.contains(&resolution.to_lowercase().as_str()) {
+        "canceled".to_string()
+    } else {
+        category
+    }
+}
+
+fn indices(headers: &StringRecord, names: &[&str]) -> Vec<usize> {
+    names
+        .iter()
+        .flat_map(|name| column_indices(headers, name))
+        .collect()
+}
+
+impl TicketImporter for JiraImporter {
+    fn read(&self) -> Result<Vec<ImportedTicketDTO>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_path(&self.path)?;
+        let headers = reader.headers()?.clone();
+        let key = indices(&headers, &["Issue key"]);
+        let summary = indices(&headers, &["Summary"]);
+        if key.is_empty() || summary.is_empty() {
+            return Err(eyre!(
+                "{} does not look like a Jira CSV export (missing 'Issue key' or 'Summary')",
+                self.path
+            ));
+        }
+        let status = indices(&headers, &["Status"]);
+        let status_category = indices(&headers, &["Status Category"]);
+        let resolution = indices(&headers, &["Resolution"]);
+        let estimate = indices(
+            &headers,
+            &[
+                "Custom field (Story Points)",
+                "Custom field (Story point estimate)",
+                "Story Points",
+                "Story point estimate",
+            ],
+        );
+        let priority = indices(&headers, &["Priority"]);
+        let assignee = indices(&headers, &["Assignee"]);
+        let labels = indices(&headers, &["Labels"]);
+        let due = indices(&headers, &["Due Date", "Due date"]);
+        let created = indices(&headers, &["Created"]);
+        let resolved = indices(&headers, &["Resolved"]);
+
+        let mut tickets = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let status_name = first_value(&record, &status);
+            tickets.push(ImportedTicketDTO {
+                external_id: first_value(&record, &key),
+                title: first_value(&record, &summary),
+                category: category(
+                    &status_name,
+                    &first_value(&record, &status_category),
+                    &first_value(&record, &resolution),
+                ),
+                status: status_name,
+                estimate: first_value(&record, &estimate),
+                priority: normalize_priority(&first_value(&record, &priority)),
+                assignee: first_value(&record, &assignee),
+                // ラベルは1つずつ別の列に書き出される
+                tags: labels
+                    .iter()
+                    .filter_map(|index| record.get(*index))
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+                    .map(String::from)
+                    .collect(),
+                due: parse_date(&first_value(&record, &due)),
+                created_at: parse_datetime(&first_value(&record, &created)),
+                resolved_at: parse_datetime(&first_value(&record, &resolved)),
+            });
+        }
+        Ok(tickets)
+    }
+}
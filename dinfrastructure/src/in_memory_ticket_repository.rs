@@ -0,0 +1,79 @@
+use crate::migrations;
+use crate::TicketCollection;
+use color_eyre::Result;
+use ddomain::domain_errors::DomainError;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint, ticket::Ticket};
+use ddomain::repositories::ticket_repository::TicketRepository;
+use std::sync::RwLock;
+
+// ファイルを使わないリポジトリ。他のツールへの組み込みやテスト向けで、読み書きの結果は TicketRepositoryImpl と同じ
+#[derive(Debug, Default)]
+pub struct InMemoryTicketRepository {
+    collection: RwLock<TicketCollection>,
+}
+
+impl InMemoryTicketRepository {
+    pub fn new(tickets: Vec<Ticket>) -> Self {
+        Self::from_collection(TicketCollection {
+            ticket_data: tickets,
+            ..TicketCollection::default()
+        })
+    }
+
+    pub fn from_collection(collection: TicketCollection) -> Self {
+        Self {
+            collection: RwLock::new(collection),
+        }
+    }
+
+    // チケットファイルと同じ書式の TOML から作る。古い版の書式は移行してから読み込む
+    pub fn from_fixture(fixture: &str) -> Result<Self, DomainError> {
+        Ok(Self::from_collection(migrations::collection_from_document(
+            toml::from_str(fixture)?,
+        )?))
+    }
+
+    pub fn with_header(self, header: FileHeader) -> Self {
+        self.collection.write().unwrap().header = header;
+        self
+    }
+
+    pub fn with_sprints(self, sprints: Vec<Sprint>) -> Self {
+        self.collection.write().unwrap().sprint_data = sprints;
+        self
+    }
+
+    // 現在の内容の複製
+    pub fn collection(&self) -> TicketCollection {
+        self.collection.read().unwrap().clone()
+    }
+}
+
+impl TicketRepository for InMemoryTicketRepository {
+    fn fetch_tickets(&self) -> Result<Vec<Ticket>, DomainError> {
+        Ok(self.collection.read().unwrap().resolved_tickets())
+    }
+
+    fn save_tickets(&self, tickets: &[Ticket]) -> Result<(), DomainError> {
+        self.collection.write().unwrap().ticket_data = tickets.to_vec();
+        Ok(())
+    }
+
+    fn fetch_sprints(&self) -> Result<Vec<Sprint>, DomainError> {
+        Ok(self.collection.read().unwrap().sprint_data.clone())
+    }
+
+    fn save_sprints(&self, sprints: &[Sprint]) -> Result<(), DomainError> {
+        self.collection.write().unwrap().sprint_data = sprints.to_vec();
+        Ok(())
+    }
+
+    fn fetch_header(&self) -> Result<FileHeader, DomainError> {
+        Ok(self.collection.read().unwrap().header.clone())
+    }
+
+    // 常に存在する
+    fn ensure_file_exists_with_template(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,9 +1,48 @@
+pub mod code_scanner;
+pub mod collection_store;
+pub mod exporters;
+pub mod git_cli;
+pub mod importers;
+pub mod in_memory_ticket_repository;
+pub mod merge_driver;
+pub mod migrations;
+pub mod storage_backend;
 pub mod ticket_repository_impl;
-use ddomain::entites::ticket::Ticket;
-use serde::Deserialize;
+pub mod user_identity;
+pub mod workspace_repository;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint, ticket::Ticket};
+use serde::{Deserialize, Serialize};
 
-// tomlパース用
-#[derive(Deserialize, Debug)]
+// ファイル全体の内容(どの保存先でも同じ構造)
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct TicketCollection {
+    #[serde(default, skip_serializing_if = "FileHeader::is_empty")]
+    pub header: FileHeader,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sprint_data: Vec<Sprint>,
     pub ticket_data: Vec<Ticket>,
 }
+
+impl Default for TicketCollection {
+    // 新しく作る内容は現在の版
+    fn default() -> Self {
+        TicketCollection {
+            header: FileHeader {
+                version: migrations::CURRENT_VERSION,
+                ..FileHeader::default()
+            },
+            sprint_data: Vec::new(),
+            ticket_data: Vec::new(),
+        }
+    }
+}
+
+impl TicketCollection {
+    // 独自のステータスの分類と見積もりのポイントをヘッダーから補ったチケット
+    pub fn resolved_tickets(&self) -> Vec<Ticket> {
+        let mut tickets = self.ticket_data.clone();
+        self.header.workflow().classify(&mut tickets);
+        self.header.resolve_levels(&mut tickets);
+        tickets
+    }
+}
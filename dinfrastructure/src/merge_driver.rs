@@ -0,0 +1,361 @@
+use crate::collection_store::document_store::{DocumentFormat, DocumentStore};
+use crate::collection_store::CollectionStore;
+use crate::migrations::CURRENT_VERSION;
+use crate::TicketCollection;
+use ddomain::domain_errors::DomainError;
+use ddomain::services::ticket_ids;
+use std::fs;
+use toml::{Table, Value};
+use toml_edit::{ArrayOfTables, ImDocument, Item, Key, TableLike};
+
+// git のマージドライバとしての結果。conflicts が1以上なら git には競合として伝える
+pub struct MergeOutcome {
+    pub conflicts: usize,
+    // 一方で削除され他方で編集されたチケットなど、自動で判断した内容
+    pub notes: Vec<String>,
+}
+
+// 競合した値の位置(ファイルの先頭からのキーと配列の位置)
+#[derive(Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+struct Conflict {
+    path: Vec<Segment>,
+    // 競合を含むチケットのID。マーカーの見出しに使う
+    ticket: Option<String>,
+    theirs: Value,
+}
+
+struct Merger {
+    conflicts: Vec<Conflict>,
+    notes: Vec<String>,
+}
+
+// 配列の要素を対応付けるキー。チケットは ID、スプリントやヘッダーの定義は名前で対応付ける
+fn identity(key: &str, table: &Table) -> Option<String> {
+    let field = |name: &str| table.get(name).map(Value::to_string);
+    match key {
+        "comments" => Some(format!("{}@{}", field("author")?, field("created_at")?)),
+        "time_entries" => field("start"),
+        _ => field("id").or_else(|| field("name")),
+    }
+}
+
+fn tables(values: &[Value]) -> Option<Vec<&Table>> {
+    values.iter().map(Value::as_table).collect()
+}
+
+impl Merger {
+    fn merge_table(
+        &mut self,
+        base: &Table,
+        ours: &Table,
+        theirs: &Table,
+        path: &[Segment],
+        ticket: Option<&str>,
+    ) -> Table {
+        // 自分の側のキーの順序を保ち、相手の側で増えたキーは末尾に加える
+        let keys = ours
+            .keys()
+            .chain(theirs.keys().filter(|key| !ours.contains_key(*key)))
+            .chain(
+                base.keys()
+                    .filter(|key| !ours.contains_key(*key) && !theirs.contains_key(*key)),
+            );
+        let mut merged = Table::new();
+        for key in keys {
+            let mut key_path = path.to_vec();
+            key_path.push(Segment::Key(key.clone()));
+            if let Some(value) = self.merge_value(
+                key,
+                base.get(key),
+                ours.get(key),
+                theirs.get(key),
+                &key_path,
+                ticket,
+            ) {
+                merged.insert(key.clone(), value);
+            }
+        }
+        merged
+    }
+
+    fn merge_value(
+        &mut self,
+        key: &str,
+        base: Option<&Value>,
+        ours: Option<&Value>,
+        theirs: Option<&Value>,
+        path: &[Segment],
+        ticket: Option<&str>,
+    ) -> Option<Value> {
+        if ours == theirs || theirs == base {
+            return ours.cloned();
+        }
+        if ours == base {
+            return theirs.cloned();
+        }
+        let empty_table = Table::new();
+        let empty_array = Vec::new();
+        match (ours, theirs) {
+            (Some(Value::Table(o)), Some(Value::Table(t))) => {
+                let b = base.and_then(Value::as_table).unwrap_or(&empty_table);
+                return Some(Value::Table(self.merge_table(b, o, t, path, ticket)));
+            }
+            (Some(Value::Array(o)), Some(Value::Array(t))) => {
+                let b = base.and_then(Value::as_array).unwrap_or(&empty_array);
+                if let Some(merged) = self.merge_array(key, b, o, t, path, ticket) {
+                    return Some(Value::Array(merged));
+                }
+            }
+            // 一方で消され他方で変えられた項目は、変えられた値を残す
+            (Some(kept), None) | (None, Some(kept)) => {
+                self.notes.push(format!(
+                    "{} of {} was removed on one side and changed on the other; kept the changed value.",
+                    key,
+                    ticket.unwrap_or("the header")
+                ));
+                return Some(kept.clone());
+            }
+            _ => {}
+        }
+        self.conflicts.push(Conflict {
+            path: path.to_vec(),
+            ticket: ticket.map(str::to_string),
+            theirs: theirs.cloned()?,
+        });
+        ours.cloned()
+    }
+
+    // 表の配列は要素を対応付けて要素ごとに、値の配列は集合として併合する。
+    // 対応付けられない表の配列は None(配列全体を1つの値として扱う)
+    fn merge_array(
+        &mut self,
+        key: &str,
+        base: &[Value],
+        ours: &[Value],
+        theirs: &[Value],
+        path: &[Segment],
+        ticket: Option<&str>,
+    ) -> Option<Vec<Value>> {
+        let all = base.iter().chain(ours).chain(theirs);
+        if !all.clone().any(Value::is_table) {
+            let in_base = |value: &Value| base.contains(value);
+            let mut merged: Vec<Value> = ours
+                .iter()
+                .filter(|value| !in_base(value) || theirs.contains(value))
+                .cloned()
+                .collect();
+            for value in theirs.iter().filter(|value| !in_base(value)) {
+                if !merged.contains(value) {
+                    merged.push(value.clone());
+                }
+            }
+            return Some(merged);
+        }
+
+        let (base, ours, theirs) = (tables(base)?, tables(ours)?, tables(theirs)?);
+        let id_of = |table: &Table| identity(key, table);
+        if all
+            .filter_map(Value::as_table)
+            .any(|table| id_of(table).is_none())
+        {
+            return None;
+        }
+        fn find<'a>(key: &str, tables: &[&'a Table], id: &str) -> Option<&'a Table> {
+            tables
+                .iter()
+                .find(|table| identity(key, table).as_deref() == Some(id))
+                .copied()
+        }
+        let ids: Vec<String> = ours
+            .iter()
+            .chain(
+                theirs
+                    .iter()
+                    .filter(|t| find(key, &ours, &id_of(t).unwrap()).is_none()),
+            )
+            .filter_map(|table| id_of(table))
+            .collect();
+
+        let mut merged = Vec::new();
+        let mut renumbered: Vec<String> = Vec::new();
+        for id in ids {
+            let (b, o, t) = (
+                find(key, &base, &id),
+                find(key, &ours, &id),
+                find(key, &theirs, &id),
+            );
+            // 両方の側で同じIDのチケットが追加された場合は、別のチケットとして両方を残し相手の側の番号を振り直す
+            if let (None, Some(o), Some(t), "ticket_data") = (b, o, t, key) {
+                if o != t {
+                    let old_id = t.get("id").and_then(Value::as_str).unwrap_or_default();
+                    let taken = base
+                        .iter()
+                        .chain(&ours)
+                        .chain(&theirs)
+                        .filter_map(|table| table.get("id")?.as_str())
+                        .chain(renumbered.iter().map(String::as_str));
+                    let new_id = ticket_ids::next_free_id(taken, ticket_ids::prefix_of(old_id));
+                    self.notes.push(format!(
+                        "{} was added on both sides; renumbered theirs to {}.",
+                        old_id, new_id
+                    ));
+                    let mut t = t.clone();
+                    t.insert("id".to_string(), Value::String(new_id.clone()));
+                    renumbered.push(new_id);
+                    merged.push(Value::Table(o.clone()));
+                    merged.push(Value::Table(t));
+                    continue;
+                }
+            }
+            // チケットの中の競合は見出しにチケットのIDを出す
+            let ticket_id = match key {
+                "ticket_data" => o.or(t).and_then(|table| table.get("id")?.as_str()),
+                _ => ticket,
+            };
+            let mut item_path = path.to_vec();
+            item_path.push(Segment::Index(merged.len()));
+            let table = match (b, o, t) {
+                (_, Some(o), Some(t)) => {
+                    let empty = Table::new();
+                    self.merge_table(b.unwrap_or(&empty), o, t, &item_path, ticket_id)
+                }
+                // 一方で削除され、他方で変更が無ければ削除する。変更がある場合は変更を残す
+                (Some(b), Some(kept), None) | (Some(b), None, Some(kept)) => {
+                    if kept == b {
+                        continue;
+                    }
+                    self.notes.push(format!(
+                        "{} was deleted on one side and edited on the other; kept the edited version.",
+                        id.trim_matches('"')
+                    ));
+                    kept.clone()
+                }
+                (None, Some(added), None) | (None, None, Some(added)) => added.clone(),
+                (_, None, None) => continue,
+            };
+            merged.push(Value::Table(table));
+        }
+        Some(merged)
+    }
+}
+
+fn read_table(path: &str) -> Result<Table, DomainError> {
+    // 共通の祖先が無い場合、git は空のファイルを渡す
+    let content = fs::read_to_string(path).map_err(DomainError::FileRead)?;
+    if content.trim().is_empty() {
+        return Ok(Table::new());
+    }
+    let table: Table = toml::from_str(&content)?;
+    // 新しい版のファイルは知らない項目を落とさないよう、マージせずに失敗させる
+    let version = table
+        .get("header")
+        .and_then(|header| header.get("version"))
+        .and_then(Value::as_integer)
+        .unwrap_or(1);
+    if version > i64::from(CURRENT_VERSION) {
+        let version = u32::try_from(version).unwrap_or(u32::MAX);
+        return Err(DomainError::UnsupportedVersion(version, CURRENT_VERSION));
+    }
+    Ok(table)
+}
+
+// 値を `key = value` の1行で表す(表はインライン表で書く)
+fn render_line(key: &str, value: &Value) -> String {
+    format!("{} = {}", Key::new(key), value)
+}
+
+// 位置を含む行の先頭と、終わりを含む行の末尾(改行を含む)
+fn line_range(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[end..].find('\n').map_or(text.len(), |i| end + i + 1);
+    (line_start, line_end)
+}
+
+// 競合した値の親の表。インライン表の中など、行単位で囲めない位置の場合は None
+fn locate<'a>(document: &'a toml_edit::Table, parents: &[Segment]) -> Option<&'a dyn TableLike> {
+    let mut parent: &dyn TableLike = document;
+    let mut array: Option<&ArrayOfTables> = None;
+    for segment in parents {
+        match segment {
+            Segment::Key(name) => match parent.get(name)? {
+                Item::Table(table) => parent = table,
+                Item::ArrayOfTables(tables) => array = Some(tables),
+                _ => return None,
+            },
+            Segment::Index(index) => parent = array.take()?.get(*index)?,
+        }
+    }
+    Some(parent)
+}
+
+// 書き出したファイルの中で競合した値の行を探し、競合マーカーで囲む
+fn insert_markers(text: &str, conflicts: &[Conflict]) -> Option<String> {
+    let document = ImDocument::parse(text.to_string()).ok()?;
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    for conflict in conflicts {
+        let (Segment::Key(key), parents) = conflict.path.split_last()? else {
+            return None;
+        };
+        let (ours_key, ours_item) = locate(document.as_table(), parents)?.get_key_value(key)?;
+        let (start, end) = line_range(text, ours_key.span()?.start, ours_item.span()?.end);
+        edits.push((
+            start,
+            end,
+            format!(
+                "<<<<<<< ours ({0})\n{1}=======\n{2}\n>>>>>>> theirs ({0})\n",
+                conflict.ticket.as_deref().unwrap_or("header"),
+                &text[start..end],
+                render_line(key, &conflict.theirs)
+            ),
+        ));
+    }
+    // 後ろから置き換えて位置がずれないようにする
+    edits.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    let mut result = text.to_string();
+    for (start, end, replacement) in edits {
+        result.replace_range(start..end, &replacement);
+    }
+    Some(result)
+}
+
+// チケットファイルを ID ごと・項目ごとに三方向でマージし、結果を ours に書き込む。
+// 両方が同じ項目を別の値に変えた場合だけ、その行を競合マーカーで囲む
+pub fn merge_files(base: &str, ours: &str, theirs: &str) -> Result<MergeOutcome, DomainError> {
+    let (base_table, ours_table, theirs_table) =
+        (read_table(base)?, read_table(ours)?, read_table(theirs)?);
+    let mut merger = Merger {
+        conflicts: Vec::new(),
+        notes: Vec::new(),
+    };
+    let merged = merger.merge_table(&base_table, &ours_table, &theirs_table, &[], None);
+
+    // 自分の側のファイルの並びとコメントを保ったまま書き込む
+    let store = DocumentStore::new(ours.to_string(), DocumentFormat::Toml);
+    match Value::Table(merged.clone()).try_into::<TicketCollection>() {
+        Ok(collection) => store.store(&collection)?,
+        Err(_) => fs::write(ours, toml::to_string(&merged)?).map_err(DomainError::FileWrite)?,
+    }
+
+    if !merger.conflicts.is_empty() {
+        // 手で書いたインライン表の中の競合は、インライン表を使わずに書き直したファイルに入れる
+        let written = fs::read_to_string(ours).map_err(DomainError::FileRead)?;
+        let marked = insert_markers(&written, &merger.conflicts)
+            .or_else(|| insert_markers(&toml::to_string(&merged).ok()?, &merger.conflicts))
+            .ok_or_else(|| {
+                DomainError::DocumentSerialize(
+                    "TOML".to_string(),
+                    "could not place the conflict markers".to_string(),
+                )
+            })?;
+        fs::write(ours, marked).map_err(DomainError::FileWrite)?;
+    }
+    Ok(MergeOutcome {
+        conflicts: merger.conflicts.len(),
+        notes: merger.notes,
+    })
+}
@@ -0,0 +1,73 @@
+use crate::collection_store::CollectionStore;
+use crate::TicketCollection;
+use ddomain::domain_errors::DomainError;
+use serde_json::{json, Map, Value};
+
+// このバイナリが読み書きするファイルの書式の版
+pub const CURRENT_VERSION: u32 = 2;
+
+// MIGRATIONS[n] は版 n+1 を版 n+2 に上げる
+const MIGRATIONS: [fn(&mut Map<String, Value>); 1] = [v1_to_v2];
+
+// 版1: 版を記録する前のファイル。ヘッダーのみのファイルでも読めるように ticket_data を補う
+fn v1_to_v2(document: &mut Map<String, Value>) {
+    document.entry("ticket_data").or_insert_with(|| json!([]));
+}
+
+// 記録された版。記録が無い場合は版1。1以上の整数でない場合は読み込まない
+pub fn version_of(document: &Value) -> Result<u32, DomainError> {
+    let Some(value) = document.pointer("/header/version") else {
+        return Ok(1);
+    };
+    match value.as_u64() {
+        // 版として表せないほど大きい値は、このバイナリより新しい版とみなす
+        Some(version) if version > u64::from(u32::MAX) => {
+            Err(DomainError::UnsupportedVersion(u32::MAX, CURRENT_VERSION))
+        }
+        Some(version) if version >= 1 => Ok(version as u32),
+        _ => Err(DomainError::DocumentParse(
+            "header".to_string(),
+            format!("'{}' is not a format version", value),
+        )),
+    }
+}
+
+// 保存先から読んだ内容を現在の版まで順に上げる。移行前の版を返す
+pub fn migrate(document: &mut Value) -> Result<u32, DomainError> {
+    let version = version_of(document)?;
+    if version > CURRENT_VERSION {
+        return Err(DomainError::UnsupportedVersion(version, CURRENT_VERSION));
+    }
+    let Value::Object(root) = document else {
+        return Err(DomainError::EmptyFile);
+    };
+    for migration in &MIGRATIONS[(version - 1) as usize..] {
+        migration(root);
+    }
+
+    let header = root
+        .entry("header")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !header.is_object() {
+        *header = Value::Object(Map::new());
+    }
+    header["version"] = json!(CURRENT_VERSION);
+    Ok(version)
+}
+
+// 移行済みの内容からチケットの集まりを作る
+pub fn collection_from_document(mut document: Value) -> Result<TicketCollection, DomainError> {
+    migrate(&mut document)?;
+    serde_json::from_value(document)
+        .map_err(|e| DomainError::DocumentParse("ticket".to_string(), e.to_string()))
+}
+
+// 古い版の保存先を現在の版で書き直す。移行前の版を返す
+pub fn upgrade(store: &dyn CollectionStore) -> Result<u32, DomainError> {
+    let mut document = store.load_document()?;
+    let version = migrate(&mut document)?;
+    if version < CURRENT_VERSION {
+        store.store(&collection_from_document(document)?)?;
+    }
+    Ok(version)
+}
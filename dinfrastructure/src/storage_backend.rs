@@ -0,0 +1,85 @@
+use crate::collection_store::document_store::{DocumentFormat, DocumentStore};
+use crate::collection_store::sqlite_store::SqliteStore;
+use crate::collection_store::CollectionStore;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum StorageBackend {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+    Sqlite,
+}
+
+impl StorageBackend {
+    // 拡張子から判断する。不明な拡張子は TOML として扱う
+    pub fn from_path(path: &str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("json") => StorageBackend::Json,
+            Some("yaml") | Some("yml") => StorageBackend::Yaml,
+            Some("db") | Some("sqlite") | Some("sqlite3") => StorageBackend::Sqlite,
+            _ => StorageBackend::Toml,
+        }
+    }
+
+    // いずれかの保存形式の拡張子を持つか
+    pub fn has_known_extension(path: &str) -> bool {
+        matches!(
+            Path::new(path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase)
+                .as_deref(),
+            Some("toml" | "json" | "yaml" | "yml" | "db" | "sqlite" | "sqlite3")
+        )
+    }
+
+    // 拡張子を省略したファイル名に補う拡張子
+    pub fn extension(&self) -> &'static str {
+        match self {
+            StorageBackend::Toml => "toml",
+            StorageBackend::Json => "json",
+            StorageBackend::Yaml => "yaml",
+            StorageBackend::Sqlite => "db",
+        }
+    }
+
+    pub fn open(&self, file_path: String) -> Box<dyn CollectionStore> {
+        match self {
+            StorageBackend::Toml => Box::new(DocumentStore::new(file_path, DocumentFormat::Toml)),
+            StorageBackend::Json => Box::new(DocumentStore::new(file_path, DocumentFormat::Json)),
+            StorageBackend::Yaml => Box::new(DocumentStore::new(file_path, DocumentFormat::Yaml)),
+            StorageBackend::Sqlite => Box::new(SqliteStore::new(file_path)),
+        }
+    }
+}
+
+impl From<String> for StorageBackend {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "json" => StorageBackend::Json,
+            "yaml" => StorageBackend::Yaml,
+            "sqlite" => StorageBackend::Sqlite,
+            _ => StorageBackend::Toml,
+        }
+    }
+}
+
+// 別の形式に書き写し、読み直した内容が元と一致することを確かめる。書き写したチケットの数を返す
+pub fn convert(source: &dyn CollectionStore, target: &dyn CollectionStore) -> Result<usize> {
+    let collection = source.load()?;
+    target.store(&collection)?;
+    let written = target.load()?;
+    if serde_json::to_value(&collection)? != serde_json::to_value(&written)? {
+        return Err(eyre!("the converted collection differs from the source"));
+    }
+    Ok(collection.ticket_data.len())
+}
@@ -1,66 +1,112 @@
+use crate::collection_store::CollectionStore;
+use crate::storage_backend::StorageBackend;
 use crate::TicketCollection;
 use color_eyre::Result;
 use ddomain::domain_errors::DomainError;
-use ddomain::entites::ticket::Ticket;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint, ticket::Ticket};
 use ddomain::repositories::ticket_repository::TicketRepository;
-use serde::Deserialize;
 use std::fs;
-use std::path::Path;
 use std::sync::{Arc, RwLock};
-use toml;
+use std::time::SystemTime;
+
+// ファイルの更新日時と大きさ。他のプロセスによる変更の検出に使う
+type FileStamp = (SystemTime, u64);
+
+// 読み込んだ時点のファイルの状態と、その時のチケット
+type TicketCache = Option<(FileStamp, Vec<Ticket>)>;
 
 pub struct TicketRepositoryImpl {
     file_path: String,
-    ticket_cache: Arc<RwLock<Vec<Ticket>>>, // チケットキャッシュ
+    store: Box<dyn CollectionStore>,
+    ticket_cache: Arc<RwLock<TicketCache>>, // チケットキャッシュ
 }
 
 impl TicketRepositoryImpl {
+    // 保存形式は拡張子から判断する
     pub fn new(file_path: String) -> Self {
+        let backend = StorageBackend::from_path(&file_path);
+        Self::with_backend(file_path, backend)
+    }
+
+    pub fn with_backend(file_path: String, backend: StorageBackend) -> Self {
         Self {
+            store: backend.open(file_path.clone()),
             file_path,
-            ticket_cache: Arc::new(RwLock::new(Vec::new())), // 空のキャッシュで初期化
+            ticket_cache: Arc::new(RwLock::new(None)), // 空のキャッシュで初期化
         }
     }
 
-    fn deserial_toml_file<T>(&self) -> Result<T, DomainError>
-    where
-        T: for<'a> Deserialize<'a>,
-    {
-        let file_str = fs::read_to_string(&self.file_path).map_err(DomainError::FileRead)?;
-        if file_str.trim().is_empty() {
-            Err(DomainError::EmptyFile)
-        } else {
-            toml::from_str(&file_str).map_err(DomainError::TomlParse)
-        }
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn file_stamp(&self) -> Option<FileStamp> {
+        let metadata = fs::metadata(&self.file_path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    fn cache_tickets(&self, tickets: Vec<Ticket>, stamp: Option<FileStamp>) {
+        let mut cache = self.ticket_cache.write().unwrap();
+        *cache = stamp.map(|stamp| (stamp, tickets));
     }
 
     fn load_tickets_from_file(&self) -> Result<Vec<Ticket>, DomainError> {
-        let ticket_collection: TicketCollection = self.deserial_toml_file::<TicketCollection>()?;
-        Ok(ticket_collection.ticket_data)
+        Ok(self.store.load()?.resolved_tickets())
+    }
+
+    // ファイル全体を読み込み、変更を加えてから書き戻す。書き戻した内容を返す
+    fn update_collection<F>(&self, update: F) -> Result<TicketCollection, DomainError>
+    where
+        F: FnOnce(&mut TicketCollection),
+    {
+        let mut ticket_collection = self.store.load()?;
+        update(&mut ticket_collection);
+        self.store.store(&ticket_collection)?;
+        Ok(ticket_collection)
     }
 }
 
 impl TicketRepository for TicketRepositoryImpl {
     fn fetch_tickets(&self) -> Result<Vec<Ticket>, DomainError> {
-        // キャッシュが空でないかチェック
-        let cache = self.ticket_cache.read().unwrap();
-        if cache.is_empty() {
-            // キャッシュが空の場合、ファイルから読み込んでキャッシュに保存
-            drop(cache);
-            let tickets = self.load_tickets_from_file()?;
-            let mut cache = self.ticket_cache.write().unwrap();
-            *cache = tickets.clone(); // チケットをキャッシュに保存
-            Ok(tickets)
-        } else {
-            // キャッシュが既にある場合、それを返す
-            Ok(cache.clone())
+        // ファイルが読み込んだ時から変わっていなければキャッシュを返す
+        let stamp = self.file_stamp();
+        if let Some((cached_stamp, tickets)) = self.ticket_cache.read().unwrap().as_ref() {
+            if stamp.as_ref() == Some(cached_stamp) {
+                return Ok(tickets.clone());
+            }
         }
+        // TUI や git pull など、他で変更された場合は読み直す
+        let tickets = self.load_tickets_from_file()?;
+        self.cache_tickets(tickets.clone(), stamp);
+        Ok(tickets)
+    }
+
+    fn save_tickets(&self, tickets: &[Ticket]) -> Result<(), DomainError> {
+        let collection =
+            self.update_collection(|collection| collection.ticket_data = tickets.to_vec())?;
+        // 書き込みに成功した内容でキャッシュを更新
+        self.cache_tickets(collection.resolved_tickets(), self.file_stamp());
+        Ok(())
+    }
+
+    fn fetch_sprints(&self) -> Result<Vec<Sprint>, DomainError> {
+        let ticket_collection = self.store.load()?;
+        Ok(ticket_collection.sprint_data)
     }
-    fn ensure_file_exists_with_template(&self) -> Result<()> {
-        let path = Path::new(&self.file_path);
 
+    fn save_sprints(&self, sprints: &[Sprint]) -> Result<(), DomainError> {
+        self.update_collection(|collection| collection.sprint_data = sprints.to_vec())?;
+        Ok(())
+    }
+
+    fn fetch_header(&self) -> Result<FileHeader, DomainError> {
+        let ticket_collection = self.store.load()?;
+        Ok(ticket_collection.header)
+    }
+
+    fn ensure_file_exists_with_template(&self) -> Result<()> {
         // ファイルが存在しない場合にエラーを返す
-        if !path.exists() {
+        if !self.store.exists() {
             return Err(DomainError::FileNotFound(self.file_path.clone()).into());
         }
 
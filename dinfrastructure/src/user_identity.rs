@@ -0,0 +1,25 @@
+use std::env;
+use std::process::Command;
+
+fn git_config(key: &str) -> Option<String> {
+    let output = Command::new("git").args(["config", key]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+// 現在のユーザーを表す候補を優先度順に返す(git の user.email, user.name, $USER)
+pub fn current_user_identities() -> Vec<String> {
+    [
+        git_config("user.email"),
+        git_config("user.name"),
+        env::var("USER").ok(),
+        env::var("USERNAME").ok(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|identity| !identity.is_empty())
+    .collect()
+}
@@ -0,0 +1,334 @@
+use crate::storage_backend::StorageBackend;
+use crate::ticket_repository_impl::TicketRepositoryImpl;
+use color_eyre::Result;
+use ddomain::domain_errors::DomainError;
+use ddomain::entites::{file_header::FileHeader, sprint::Sprint, ticket::Ticket};
+use ddomain::repositories::ticket_repository::TicketRepository;
+use ddomain::services::ticket_ids;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// 複数のチケットファイルを1つのワークスペースとして扱う。
+// 各ファイルの読み書きはそれぞれの TicketRepositoryImpl に任せ、チケットは所属プロジェクトで振り分ける
+pub struct WorkspaceRepository {
+    // (プロジェクト名, そのファイルのリポジトリ) を指定した順に並べたもの
+    projects: Vec<(String, TicketRepositoryImpl)>,
+}
+
+// ヘッダーかチケットの一覧を持つ文書だけをチケットファイルとみなす
+fn is_ticket_file(path: &str) -> bool {
+    StorageBackend::from_path(path)
+        .open(path.to_string())
+        .load_document()
+        .is_ok_and(|document| {
+            document.get("header").is_some() || document.get("ticket_data").is_some()
+        })
+}
+
+// ディレクトリの場合は直下のチケットファイルを名前順に並べる。
+// 拡張子が対応していても、チケットファイルではないもの(Cargo.toml など)は含めない
+pub fn workspace_files(paths: &[String]) -> Result<Vec<String>, DomainError> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !Path::new(path).is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut found: Vec<String> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().to_string())
+            .filter(|path| StorageBackend::has_known_extension(path))
+            .filter(|path| is_ticket_file(path))
+            .collect();
+        found.sort();
+        files.extend(found);
+    }
+    Ok(files)
+}
+
+impl WorkspaceRepository {
+    // プロジェクト名は拡張子を除いたファイル名。同じ名前がある場合は拡張子を含める
+    pub fn new(repositories: Vec<TicketRepositoryImpl>) -> Self {
+        let mut projects: Vec<(String, TicketRepositoryImpl)> = Vec::new();
+        for repository in repositories {
+            let path = Path::new(repository.file_path());
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let name = if projects.iter().any(|(name, _)| *name == stem) {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or(stem)
+            } else {
+                stem
+            };
+            projects.push((name, repository));
+        }
+        Self { projects }
+    }
+
+    // プロジェクトの指定が無いチケット(新しく作ったものなど)は最初のファイルに保存する
+    fn belongs_to(&self, ticket: &Ticket, project: &str) -> bool {
+        match &ticket.project {
+            Some(name) => name == project,
+            None => self
+                .projects
+                .first()
+                .is_some_and(|(first, _)| first == project),
+        }
+    }
+
+    // "プロジェクト名/ID" の形のIDを (プロジェクトの位置, ファイルでのID) に分ける
+    fn split_qualified<'a>(&self, id: &'a str) -> Option<(usize, &'a str)> {
+        let (project, id) = id.split_once('/')?;
+        let index = self.projects.iter().position(|(name, _)| name == project)?;
+        Some((index, id))
+    }
+}
+
+// 参照しているチケットのワークスペースでのID。同じファイルのチケット、"プロジェクト名/ID" の形、
+// 他のファイルのチケットの順に探し、見つからない場合はそのまま残す
+fn resolve_reference(
+    loaded: &[(String, Vec<Ticket>)],
+    workspace_id: &dyn Fn(usize, &str) -> String,
+    index: usize,
+    reference: &str,
+) -> String {
+    let holds = |tickets: &[Ticket], id: &str| tickets.iter().any(|ticket| ticket.id == id);
+    if holds(&loaded[index].1, reference) {
+        return workspace_id(index, reference);
+    }
+    if let Some((project, id)) = reference.split_once('/') {
+        if let Some(other) = loaded
+            .iter()
+            .position(|(name, tickets)| name == project && holds(tickets, id))
+        {
+            return workspace_id(other, id);
+        }
+    }
+    match loaded
+        .iter()
+        .position(|(_, tickets)| holds(tickets, reference))
+    {
+        Some(other) => workspace_id(other, reference),
+        None => reference.to_string(),
+    }
+}
+
+impl TicketRepository for WorkspaceRepository {
+    // 別々に作ったファイルはどれも DIG-1 から番号を振るため、他のファイルと同じIDを持つ
+    // プロジェクトのチケットは "プロジェクト名/ID" で区別する。参照も同じ形に書き換える
+    fn fetch_tickets(&self) -> Result<Vec<Ticket>, DomainError> {
+        let mut loaded: Vec<(String, Vec<Ticket>)> = Vec::new();
+        for (project, repository) in &self.projects {
+            loaded.push((project.clone(), repository.fetch_tickets()?));
+        }
+        let qualified: Vec<bool> = loaded
+            .iter()
+            .enumerate()
+            .map(|(index, (_, tickets))| {
+                tickets.iter().any(|ticket| {
+                    loaded.iter().enumerate().any(|(other, (_, others))| {
+                        other != index && others.iter().any(|known| known.id == ticket.id)
+                    })
+                })
+            })
+            .collect();
+        let workspace_id = |index: usize, id: &str| {
+            if qualified[index] {
+                format!("{}/{}", loaded[index].0, id)
+            } else {
+                id.to_string()
+            }
+        };
+
+        let mut tickets: Vec<Ticket> = Vec::new();
+        for (index, (project, own)) in loaded.iter().enumerate() {
+            for ticket in own {
+                let mut ticket = ticket.clone();
+                ticket.id = workspace_id(index, &ticket.id);
+                ticket.parent = ticket
+                    .parent
+                    .map(|parent| resolve_reference(&loaded, &workspace_id, index, &parent));
+                ticket.blocked_by = ticket
+                    .blocked_by
+                    .iter()
+                    .map(|blocker| resolve_reference(&loaded, &workspace_id, index, blocker))
+                    .collect();
+                // 同じファイルの中で重なっている場合は選択や親子関係を区別できない
+                if let Some(other) = tickets.iter().find(|other| other.id == ticket.id) {
+                    return Err(DomainError::DuplicateTicket(
+                        ticket.id,
+                        other.project.clone().unwrap_or_default(),
+                        project.clone(),
+                    ));
+                }
+                ticket.project = Some(project.clone());
+                tickets.push(ticket);
+            }
+        }
+        Ok(tickets)
+    }
+
+    // "プロジェクト名/ID" を各ファイルでのIDに戻す。別のプロジェクトに移したチケットは、
+    // 移動先のIDと重なる場合だけ番号を振り直す。
+    // 移したチケットが失われないよう、チケットが増えるファイルから書き込む。
+    // 読めないファイルがある場合は何も書き込まない
+    fn save_tickets(&self, tickets: &[Ticket]) -> Result<(), DomainError> {
+        let mut stored = Vec::new();
+        for (_, repository) in &self.projects {
+            stored.push(repository.fetch_tickets()?);
+        }
+        let file_id = |ticket: &Ticket| -> String {
+            self.split_qualified(&ticket.id)
+                .map_or(ticket.id.clone(), |(_, id)| id.to_string())
+        };
+
+        // ワークスペースでのID -> (保存先の位置, ファイルでのID)
+        let mut placed: HashMap<String, (usize, String)> = HashMap::new();
+        let mut owned_by: Vec<Vec<&Ticket>> = Vec::new();
+        for (index, (project, _)) in self.projects.iter().enumerate() {
+            let owned: Vec<&Ticket> = tickets
+                .iter()
+                .filter(|ticket| self.belongs_to(ticket, project))
+                .collect();
+            let stays = |ticket: &Ticket| match self.split_qualified(&ticket.id) {
+                Some((origin, _)) => origin == index,
+                None => stored[index].iter().any(|known| known.id == ticket.id),
+            };
+            // 元からこのファイルにあったチケットのIDを先に確保する
+            let mut claimed: Vec<String> = Vec::new();
+            for ticket in owned.iter().filter(|ticket| stays(ticket)) {
+                claimed.push(file_id(ticket));
+                placed.insert(ticket.id.clone(), (index, file_id(ticket)));
+            }
+            for ticket in owned.iter().filter(|ticket| !stays(ticket)) {
+                let mut id = file_id(ticket);
+                if claimed.contains(&id) {
+                    id = ticket_ids::next_free_id(
+                        claimed.iter().map(String::as_str),
+                        ticket_ids::prefix_of(&id),
+                    );
+                }
+                claimed.push(id.clone());
+                placed.insert(ticket.id.clone(), (index, id));
+            }
+            owned_by.push(owned);
+        }
+
+        // 他のファイルのチケットへの参照は、自分のファイルのIDと重なる場合だけ "プロジェクト名/ID" で書く
+        let reference = |from: usize, id: &str| -> String {
+            let Some((to, file_id)) = placed.get(id) else {
+                return id.to_string();
+            };
+            let shadowed = placed
+                .values()
+                .any(|(index, other)| *index == from && other == file_id);
+            if *to != from && shadowed {
+                format!("{}/{}", self.projects[*to].0, file_id)
+            } else {
+                file_id.clone()
+            }
+        };
+
+        let mut writes = Vec::new();
+        for (index, (_, repository)) in self.projects.iter().enumerate() {
+            let owned: Vec<Ticket> = owned_by[index]
+                .iter()
+                .map(|ticket| {
+                    let mut ticket = (*ticket).clone();
+                    ticket.id = placed[&ticket.id].1.clone();
+                    ticket.parent = ticket.parent.map(|parent| reference(index, &parent));
+                    ticket.blocked_by = ticket
+                        .blocked_by
+                        .iter()
+                        .map(|blocker| reference(index, blocker))
+                        .collect();
+                    ticket
+                })
+                .collect();
+            let gains = owned
+                .iter()
+                .any(|ticket| !stored[index].iter().any(|known| known.id == ticket.id));
+            writes.push((gains, repository, owned));
+        }
+        writes.sort_by_key(|(gains, _, _)| !*gains);
+        for (_, repository, owned) in writes {
+            repository.save_tickets(&owned)?;
+        }
+        Ok(())
+    }
+
+    // 同じ名前のスプリントは1つにまとめる
+    fn fetch_sprints(&self) -> Result<Vec<Sprint>, DomainError> {
+        let mut sprints: Vec<Sprint> = Vec::new();
+        for (_, repository) in &self.projects {
+            for sprint in repository.fetch_sprints()? {
+                if !sprints.iter().any(|known| known.name == sprint.name) {
+                    sprints.push(sprint);
+                }
+            }
+        }
+        Ok(sprints)
+    }
+
+    // 各ファイルには元々あったスプリントを書き戻す。新しいスプリントはスプリントを使っているファイルに追加する
+    fn save_sprints(&self, sprints: &[Sprint]) -> Result<(), DomainError> {
+        let mut existing = Vec::new();
+        for (_, repository) in &self.projects {
+            existing.push(repository.fetch_sprints()?);
+        }
+        let is_new = |sprint: &Sprint| {
+            !existing
+                .iter()
+                .flatten()
+                .any(|known| known.name == sprint.name)
+        };
+        let uses_sprints = existing.iter().any(|sprints| !sprints.is_empty());
+        for (index, (_, repository)) in self.projects.iter().enumerate() {
+            let own = &existing[index];
+            // どのファイルもスプリントを使っていない場合は最初のファイルに追加する
+            if !own.is_empty() || (!uses_sprints && index == 0) {
+                let updated: Vec<Sprint> = sprints
+                    .iter()
+                    .filter(|sprint| {
+                        is_new(sprint) || own.iter().any(|known| known.name == sprint.name)
+                    })
+                    .cloned()
+                    .collect();
+                repository.save_sprints(&updated)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fetch_header(&self) -> Result<FileHeader, DomainError> {
+        let mut merged: Option<FileHeader> = None;
+        for (_, repository) in &self.projects {
+            let header = repository.fetch_header()?;
+            match &mut merged {
+                Some(merged) => merged.merge(&header),
+                None => merged = Some(header),
+            }
+        }
+        Ok(merged.unwrap_or_default())
+    }
+
+    fn ensure_file_exists_with_template(&self) -> Result<()> {
+        for (_, repository) in &self.projects {
+            repository.ensure_file_exists_with_template()?;
+        }
+        Ok(())
+    }
+
+    fn projects(&self) -> Vec<String> {
+        self.projects
+            .iter()
+            .map(|(project, _)| project.clone())
+            .collect()
+    }
+}
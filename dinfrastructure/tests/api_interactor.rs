@@ -0,0 +1,121 @@
+// HTTP API のユースケースが、取得後に変更されたチケットを上書きしないこと
+use dapplication::dtos::api_dto::{TicketRequestDTO, TransitionRequestDTO};
+use dapplication::input_ports::api_input_port::ApiInputPort;
+use dapplication::interactors::api_interactor::ApiInteractor;
+use ddomain::domain_errors::DomainError;
+use ddomain::repositories::ticket_repository::TicketRepository;
+use dinfrastructure::in_memory_ticket_repository::InMemoryTicketRepository;
+use dinfrastructure::ticket_repository_impl::TicketRepositoryImpl;
+
+mod common;
+use common::TempPath;
+
+const FIXTURE: &str = r#"
+[[ticket_data]]
+id = "DIG-1"
+level = "M"
+title = "Parent"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+
+[[ticket_data]]
+id = "DIG-2"
+level = "S"
+title = "Child"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+parent = "DIG-1"
+blocked_by = ["DIG-1"]
+"#;
+
+fn api() -> ApiInteractor<InMemoryTicketRepository> {
+    ApiInteractor::new(InMemoryTicketRepository::from_fixture(FIXTURE).unwrap())
+}
+
+fn retitle(title: &str) -> TicketRequestDTO {
+    TicketRequestDTO {
+        title: Some(title.to_string()),
+        ..TicketRequestDTO::default()
+    }
+}
+
+#[test]
+fn a_stale_etag_is_rejected_without_saving() {
+    let api = api();
+    let fetched = api.get("DIG-1").unwrap();
+    let updated = api
+        .update("DIG-1", &fetched.etag, retitle("Renamed"))
+        .unwrap();
+    assert_ne!(updated.etag, fetched.etag);
+
+    let error = api
+        .update("DIG-1", &fetched.etag, retitle("Lost update"))
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<DomainError>(),
+        Some(DomainError::StaleTicket(_))
+    ));
+    let transition = TransitionRequestDTO {
+        status: "Resolved".to_string(),
+        force: false,
+    };
+    assert!(api.transition("DIG-1", &fetched.etag, transition).is_err());
+    assert!(api.delete("DIG-1", &fetched.etag).is_err());
+    assert_eq!(api.get("DIG-1").unwrap().ticket.title, "Renamed");
+}
+
+#[test]
+fn deleting_a_ticket_removes_references_to_it() {
+    let api = api();
+    let etag = api.get("DIG-1").unwrap().etag;
+    api.delete("DIG-1", &etag).unwrap();
+
+    let tickets = api.list(Default::default()).unwrap();
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0].ticket.parent, "");
+    assert!(tickets[0].ticket.blocked_by.is_empty());
+}
+
+#[test]
+fn edits_made_elsewhere_after_fetching_are_not_overwritten() {
+    let file = TempPath::file("api-external.toml", FIXTURE);
+    let api = ApiInteractor::new(TicketRepositoryImpl::new(file.text()));
+    let fetched = api.get("DIG-1").unwrap();
+
+    // TUI など別のプロセスからの変更
+    let other = TicketRepositoryImpl::new(file.text());
+    let mut tickets = other.fetch_tickets().unwrap();
+    tickets[0].title = "Edited in the terminal".to_string();
+    other.save_tickets(&tickets).unwrap();
+
+    let error = api
+        .update("DIG-1", &fetched.etag, retitle("Edited over HTTP"))
+        .unwrap_err();
+    let current = api.get("DIG-1").unwrap();
+
+    assert!(matches!(
+        error.downcast_ref::<DomainError>(),
+        Some(DomainError::StaleTicket(_))
+    ));
+    assert_eq!(current.ticket.title, "Edited in the terminal");
+    assert_ne!(current.etag, fetched.etag);
+}
+
+#[test]
+fn an_unknown_level_is_rejected_without_changing_the_estimate() {
+    let api = api();
+    let etag = api.get("DIG-1").unwrap().etag;
+    let update = |level: &str| TicketRequestDTO {
+        level: Some(level.to_string()),
+        ..TicketRequestDTO::default()
+    };
+    assert!(api.update("DIG-1", &etag, update("Fiv")).is_err());
+    assert_eq!(api.get("DIG-1").unwrap().ticket.level, "M");
+
+    let updated = api.update("DIG-1", &etag, update("Five")).unwrap();
+    assert_eq!(updated.ticket.level, "Five");
+    let updated = api.update("DIG-1", &updated.etag, update("3")).unwrap();
+    assert_eq!(updated.ticket.level, "Three");
+    let cleared = api.update("DIG-1", &updated.etag, update("")).unwrap();
+    assert_eq!(cleared.ticket.level, "?");
+}
@@ -0,0 +1,92 @@
+// ソースコードの走査で、コメントの TODO などだけを .gitignore に従って集めること
+use dapplication::output_ports::code_scanner::CodeScanner;
+use dinfrastructure::code_scanner::SourceTreeScanner;
+
+mod common;
+use common::TempPath;
+
+fn source_tree(name: &str) -> TempPath {
+    let root = TempPath::dir(&format!("scan-{}", name));
+    root.write(".gitignore", "target/\n");
+    root.write(
+        "src/main.rs",
+        concat!(
+            "fn main() {\n",
+            "    // TODO(yu): handle errors\n",
+            "    let label = \"TODO not a comment\";\n",
+            "    /* FIXME: leaks memory */\n",
+            "    // TODOS are not markers\n",
+            "}\n",
+        ),
+    );
+    root.write("deploy.sh", "#!/bin/sh\n# HACK\n");
+    root.write("target/generated.rs", "// TODO: ignored\n");
+    root
+}
+
+#[test]
+fn finds_markers_in_comments_outside_ignored_files() {
+    let root = source_tree("markers");
+    let scanner = SourceTreeScanner::new(root.text());
+    let found: Vec<String> = scanner
+        .scan()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            let file = item.location.path.rsplit('/').next().unwrap();
+            format!(
+                "{}:{} {} {}",
+                file,
+                item.location.line,
+                item.marker.kind,
+                item.marker.title()
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            "deploy.sh:2 HACK HACK",
+            "main.rs:2 TODO handle errors",
+            "main.rs:4 FIXME leaks memory",
+        ]
+    );
+}
+
+#[test]
+fn covers_only_paths_under_the_scanned_directory() {
+    let scanner = SourceTreeScanner::new("./src/".to_string());
+    assert!(scanner.covers("src/main.rs"));
+    assert!(!scanner.covers("srcs/main.rs"));
+    assert!(!scanner.covers("tests/main.rs"));
+    assert!(SourceTreeScanner::new(".".to_string()).covers("tests/main.rs"));
+}
+
+#[test]
+fn paths_are_relative_to_the_ticket_file_directory() {
+    let root = source_tree("relative");
+    let base = root.text();
+    // `digger scan t.toml .` と `cd src && digger scan ../t.toml .` に当たる走査
+    let from_root = SourceTreeScanner::new(base.clone()).relative_to(base.clone());
+    let from_src = SourceTreeScanner::new(root.path().join("src").to_string_lossy().to_string())
+        .relative_to(base.clone());
+    let paths = |scanner: &SourceTreeScanner| -> Vec<String> {
+        scanner
+            .scan()
+            .unwrap()
+            .iter()
+            .map(|item| item.location.to_string())
+            .collect()
+    };
+    let (all, in_src) = (paths(&from_root), paths(&from_src));
+    let covered = [
+        from_src.covers("src/main.rs"),
+        from_src.covers("deploy.sh"),
+        from_root.covers("deploy.sh"),
+    ];
+
+    assert_eq!(all, ["deploy.sh:2", "src/main.rs:2", "src/main.rs:4"]);
+    assert_eq!(in_src, ["src/main.rs:2", "src/main.rs:4"]);
+    assert_eq!(covered, [true, false, true]);
+}
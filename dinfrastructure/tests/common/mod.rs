@@ -0,0 +1,69 @@
+// 統合テストで共有する一時ファイルとディレクトリ
+// テストごとに使う関数が異なるため、使わない関数があっても警告しない
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 一時ディレクトリ内の、ほかのテストやプロセスと重ならないパス。破棄するときに削除する
+pub struct TempPath {
+    path: PathBuf,
+}
+
+impl TempPath {
+    // まだ何も作っていないパス(name は拡張子を含む末尾)
+    pub fn new(name: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "digger-{}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst),
+            name
+        ));
+        TempPath { path }
+    }
+
+    // contents を書き込んだファイル
+    pub fn file(name: &str, contents: &str) -> Self {
+        let temp = Self::new(name);
+        fs::write(&temp.path, contents).unwrap();
+        temp
+    }
+
+    // 空のディレクトリ
+    pub fn dir(name: &str) -> Self {
+        let temp = Self::new(name);
+        fs::create_dir_all(&temp.path).unwrap();
+        temp
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // リポジトリの生成に渡す文字列のパス
+    pub fn text(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    // ディレクトリ内に contents を書き込み、そのパスを返す
+    pub fn write(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.path.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if self.path.is_dir() {
+            let _ = fs::remove_dir_all(&self.path);
+        } else {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
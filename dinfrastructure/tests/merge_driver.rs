@@ -0,0 +1,135 @@
+// git のマージドライバとして、チケット単位・項目単位で三方向マージすること
+use ddomain::domain_errors::DomainError;
+use dinfrastructure::merge_driver::{merge_files, MergeOutcome};
+use std::fs;
+
+mod common;
+use common::TempPath;
+
+const BASE: &str = r#"[header]
+version = 2
+
+# 進行中
+[[ticket_data]]
+id = "DIG-1"
+level = "M"
+title = "First"
+status = "ToDo"
+created_at = "2026-10-01T09:00:00Z"
+
+[[ticket_data]]
+id = "DIG-2"
+level = "S"
+title = "Second"
+status = "ToDo"
+created_at = "2026-10-01T09:00:00Z"
+"#;
+
+// base、ours、theirs の内容を書き込んだ3つのファイル
+fn sides(name: &str, ours: &str, theirs: &str) -> [TempPath; 3] {
+    let side = |side: &str, contents: &str| {
+        TempPath::file(&format!("merge-{}-{}.toml", name, side), contents)
+    };
+    [
+        side("base", BASE),
+        side("ours", ours),
+        side("theirs", theirs),
+    ]
+}
+
+// base を書き換えた ours と theirs をマージし、ours に書かれた結果を返す
+fn merge(
+    name: &str,
+    ours: impl Fn(&str) -> String,
+    theirs: impl Fn(&str) -> String,
+) -> (MergeOutcome, String) {
+    let [base, ours, theirs] = sides(name, &ours(BASE), &theirs(BASE));
+    let outcome = merge_files(&base.text(), &ours.text(), &theirs.text()).unwrap();
+    (outcome, fs::read_to_string(ours.path()).unwrap())
+}
+
+#[test]
+fn edits_to_different_fields_are_combined() {
+    let (outcome, merged) = merge(
+        "combined",
+        |base| base.replace("title = \"First\"", "title = \"First (ours)\""),
+        |base| {
+            base.replace("level = \"M\"", "level = \"L\"")
+                + "\n[[ticket_data]]\nid = \"DIG-3\"\nlevel = \"S\"\ntitle = \"Added\"\nstatus = \"ToDo\"\ncreated_at = \"2026-10-02T09:00:00Z\"\n"
+        },
+    );
+    assert_eq!(outcome.conflicts, 0);
+    assert!(merged.starts_with("[header]\nversion = 2\n\n# 進行中\n"));
+    assert!(merged.contains("level = \"L\"\ntitle = \"First (ours)\"\n"));
+    assert!(merged.contains("id = \"DIG-3\""));
+}
+
+#[test]
+fn only_the_conflicting_field_is_marked() {
+    let (outcome, merged) = merge(
+        "conflict",
+        |base| base.replacen("status = \"ToDo\"", "status = \"InProgress\"", 1),
+        |base| {
+            base.replacen("status = \"ToDo\"", "status = \"Done\"", 1)
+                .replace("title = \"Second\"", "title = \"Second (theirs)\"")
+        },
+    );
+    assert_eq!(outcome.conflicts, 1);
+    assert!(merged.contains(concat!(
+        "<<<<<<< ours (DIG-1)\n",
+        "status = \"InProgress\"\n",
+        "=======\n",
+        "status = \"Done\"\n",
+        ">>>>>>> theirs (DIG-1)\n",
+    )));
+    assert!(merged.contains("title = \"Second (theirs)\""));
+}
+
+#[test]
+fn a_ticket_edited_on_one_side_survives_deletion_on_the_other() {
+    let (outcome, merged) = merge(
+        "deleted",
+        |base| base[..base.rfind("[[ticket_data]]").unwrap()].to_string(),
+        |base| base.replace("title = \"Second\"", "title = \"Second (theirs)\""),
+    );
+    assert_eq!(outcome.conflicts, 0);
+    assert_eq!(outcome.notes.len(), 1);
+    assert!(merged.contains("title = \"Second (theirs)\""));
+}
+
+#[test]
+fn tickets_added_with_the_same_id_on_both_sides_are_both_kept() {
+    let added = |title: &str| {
+        format!(
+            "\n[[ticket_data]]\nid = \"DIG-3\"\nlevel = \"S\"\ntitle = \"{}\"\nstatus = \"ToDo\"\ncreated_at = \"2026-10-02T09:00:00Z\"\n",
+            title
+        )
+    };
+    let (outcome, merged) = merge(
+        "added-twice",
+        |base| base.to_string() + &added("Ours"),
+        |base| base.to_string() + &added("Theirs"),
+    );
+    assert_eq!(outcome.conflicts, 0);
+    assert_eq!(
+        outcome.notes,
+        ["DIG-3 was added on both sides; renumbered theirs to DIG-4."]
+    );
+    assert!(merged.contains("id = \"DIG-3\"\nlevel = \"S\"\ntitle = \"Ours\""));
+    assert!(merged.contains("id = \"DIG-4\"\nlevel = \"S\"\ntitle = \"Theirs\""));
+}
+
+#[test]
+fn a_file_from_a_newer_version_is_not_merged() {
+    let ours = BASE.replace("title = \"First\"", "title = \"First (ours)\"");
+    let theirs = BASE.replace("version = 2", "version = 99") + "unknown = \"kept\"\n";
+    let [base_file, ours_file, theirs_file] = sides("newer", &ours, &theirs);
+    let error = merge_files(&base_file.text(), &ours_file.text(), &theirs_file.text()).err();
+    let untouched = fs::read_to_string(ours_file.path()).unwrap();
+
+    assert!(matches!(
+        error,
+        Some(DomainError::UnsupportedVersion(99, 2))
+    ));
+    assert_eq!(untouched, ours);
+}
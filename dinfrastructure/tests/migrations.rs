@@ -0,0 +1,34 @@
+// 読み込んだ文書の版の判定と、現在の版への移行
+use ddomain::domain_errors::DomainError;
+use dinfrastructure::migrations::{migrate, CURRENT_VERSION};
+use serde_json::json;
+
+#[test]
+fn a_document_without_a_version_is_migrated_from_version_1() {
+    let mut document = json!({ "header": { "scale": "t-shirt" } });
+    assert_eq!(migrate(&mut document).unwrap(), 1);
+    assert_eq!(document["header"]["version"], json!(CURRENT_VERSION));
+    assert_eq!(document["ticket_data"], json!([]));
+}
+
+#[test]
+fn newer_versions_are_rejected_even_beyond_u32() {
+    for version in [json!(CURRENT_VERSION + 1), json!(4294967298_u64)] {
+        let mut document = json!({ "header": { "version": version } });
+        assert!(matches!(
+            migrate(&mut document),
+            Err(DomainError::UnsupportedVersion(_, CURRENT_VERSION))
+        ));
+    }
+}
+
+#[test]
+fn a_version_that_is_not_a_positive_integer_is_rejected() {
+    for version in [json!("2"), json!(1.5), json!(0), json!(-1)] {
+        let mut document = json!({ "header": { "version": version } });
+        assert!(matches!(
+            migrate(&mut document),
+            Err(DomainError::DocumentParse(..))
+        ));
+    }
+}
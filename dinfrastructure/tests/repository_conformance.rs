@@ -0,0 +1,314 @@
+// すべての TicketRepository の実装が満たすべき振る舞い。
+// 新しい保存先を追加したら conformance_suite! に1行加える
+use ddomain::entites::ticket::Ticket;
+use ddomain::repositories::ticket_repository::TicketRepository;
+use ddomain::value_objects::status_category::StatusCategory;
+use ddomain::value_objects::ticket_level::TicketLevel;
+use ddomain::value_objects::ticket_status::TicketStatus;
+use dinfrastructure::in_memory_ticket_repository::InMemoryTicketRepository;
+use dinfrastructure::migrations::CURRENT_VERSION;
+use dinfrastructure::storage_backend::StorageBackend;
+use dinfrastructure::ticket_repository_impl::TicketRepositoryImpl;
+use dinfrastructure::workspace_repository::WorkspaceRepository;
+use dinfrastructure::TicketCollection;
+
+mod common;
+use common::TempPath;
+
+const FIXTURE: &str = r##"
+[header]
+scale = "t-shirt"
+
+[header.estimate_points]
+XL = 13
+
+[[header.tags]]
+name = "bug"
+color = "red"
+
+[[header.members]]
+id = "yu"
+name = "Yu Tokunaga"
+email = "yu@example.com"
+
+[[header.fields]]
+name = "component"
+type = "enum"
+options = ["ui", "core"]
+
+[[header.statuses]]
+name = "Backlog"
+category = "todo"
+
+[[header.statuses]]
+name = "Review"
+category = "in-progress"
+transitions = ["Shipped", "Backlog"]
+
+[[header.statuses]]
+name = "Shipped"
+category = "done"
+
+[[header.statuses]]
+name = "Dropped"
+category = "canceled"
+
+[[sprint_data]]
+name = "Sprint 1"
+start = "2026-10-05"
+end = "2026-10-18"
+goal = "first release"
+closed = false
+
+[[ticket_data]]
+id = "DIG-2"
+external_id = "GH-7"
+level = "XL"
+title = "Every field"
+status = "Review"
+priority = "High"
+rank = 2
+created_at = "2026-10-01T09:00:00Z"
+sprint = "Sprint 1"
+parent = "DIG-1"
+blocked_by = ["DIG-3"]
+tags = ["bug", "ui"]
+assignee = "yu"
+due = "2026-10-20"
+recurrence = "weekly:mon"
+
+[[ticket_data.time_entries]]
+start = "2026-10-02T09:00:00Z"
+end = "2026-10-02T10:30:00Z"
+member = "yu"
+note = "investigation"
+
+[[ticket_data.comments]]
+author = "yu"
+created_at = "2026-10-02T11:00:00Z"
+body = "Needs a second look"
+
+[ticket_data.fields]
+component = "ui"
+severity = 2.5
+
+[[ticket_data]]
+id = "DIG-1"
+level = "M"
+title = "Parent"
+status = "Shipped"
+created_at = "2026-09-30T09:00:00Z"
+resolved_at = "2026-10-03T09:00:00Z"
+
+[[ticket_data]]
+id = "DIG-3"
+level = "?"
+title = "Blocker"
+status = "Backlog"
+created_at = "2026-10-01T10:00:00Z"
+"##;
+
+// テスト中の保存先。ファイルを使う場合は終了時に削除する
+struct Seeded {
+    repository: Box<dyn TicketRepository>,
+    _files: Vec<TempPath>,
+}
+
+fn in_memory() -> Seeded {
+    Seeded {
+        repository: Box::new(InMemoryTicketRepository::from_fixture(FIXTURE).unwrap()),
+        _files: Vec::new(),
+    }
+}
+
+// collection を書き込んだ一時ファイル
+fn seeded_file(backend: StorageBackend, collection: &TicketCollection) -> TempPath {
+    let file = TempPath::new(&format!("conformance.{}", backend.extension()));
+    backend.open(file.text()).store(collection).unwrap();
+    file
+}
+
+fn file(backend: StorageBackend) -> Seeded {
+    let collection: TicketCollection = toml::from_str(FIXTURE).unwrap();
+    let file = seeded_file(backend, &collection);
+    Seeded {
+        repository: Box::new(TicketRepositoryImpl::with_backend(file.text(), backend)),
+        _files: vec![file],
+    }
+}
+
+// チケットは最初のファイルにあり、2つ目のファイルは同じヘッダーとスプリントだけを持つ
+fn workspace() -> Seeded {
+    let collection: TicketCollection = toml::from_str(FIXTURE).unwrap();
+    let empty = TicketCollection {
+        ticket_data: Vec::new(),
+        ..toml::from_str(FIXTURE).unwrap()
+    };
+    let files = vec![
+        seeded_file(StorageBackend::Toml, &collection),
+        seeded_file(StorageBackend::Json, &empty),
+    ];
+    let repositories = files
+        .iter()
+        .map(|file| TicketRepositoryImpl::new(file.text()))
+        .collect();
+    Seeded {
+        repository: Box::new(WorkspaceRepository::new(repositories)),
+        _files: files,
+    }
+}
+
+fn ids(tickets: &[Ticket]) -> Vec<&str> {
+    tickets.iter().map(|ticket| ticket.id.as_str()).collect()
+}
+
+fn find<'a>(tickets: &'a [Ticket], id: &str) -> &'a Ticket {
+    tickets.iter().find(|ticket| ticket.id == id).unwrap()
+}
+
+fn json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap()
+}
+
+fn exists(repository: &dyn TicketRepository) {
+    repository.ensure_file_exists_with_template().unwrap();
+}
+
+fn fetches_tickets_in_stored_order(repository: &dyn TicketRepository) {
+    let tickets = repository.fetch_tickets().unwrap();
+    assert_eq!(ids(&tickets), ["DIG-2", "DIG-1", "DIG-3"]);
+}
+
+fn resolves_statuses_and_levels_from_header(repository: &dyn TicketRepository) {
+    let tickets = repository.fetch_tickets().unwrap();
+    let review = find(&tickets, "DIG-2");
+    assert_eq!(review.status.category(), StatusCategory::InProgress);
+    assert_eq!(review.level.points(), 13);
+    let shipped = find(&tickets, "DIG-1");
+    assert_eq!(shipped.status.category(), StatusCategory::Done);
+    assert_eq!(shipped.level.points(), 3);
+    assert_eq!(find(&tickets, "DIG-3").level, TicketLevel::Unestimated);
+}
+
+fn fetches_header_and_sprints(repository: &dyn TicketRepository) {
+    let header = repository.fetch_header().unwrap();
+    // 版の無いファイルは現在の版に移行して読み込まれる
+    assert_eq!(header.version, CURRENT_VERSION);
+    assert_eq!(header.workflow().statuses.len(), 4);
+    assert_eq!(header.estimate_points("XL"), Some(13));
+    assert_eq!(header.members[0].id, "yu");
+    assert_eq!(header.fields[0].name, "component");
+    let sprints = repository.fetch_sprints().unwrap();
+    assert_eq!(sprints.len(), 1);
+    assert_eq!(sprints[0].goal, "first release");
+}
+
+fn round_trip_preserves_every_field(repository: &dyn TicketRepository) {
+    let before = repository.fetch_tickets().unwrap();
+    repository.save_tickets(&before).unwrap();
+    let after = repository.fetch_tickets().unwrap();
+    assert_eq!(json(&before), json(&after));
+
+    let ticket = find(&after, "DIG-2");
+    assert_eq!(ticket.external_id.as_deref(), Some("GH-7"));
+    assert_eq!(ticket.rank, 2);
+    assert_eq!(ticket.time_entries.len(), 1);
+    assert_eq!(ticket.comments[0].body, "Needs a second look");
+    assert_eq!(ticket.fields.len(), 2);
+    assert!(ticket.recurrence.is_some());
+}
+
+fn saving_tickets_keeps_header_and_sprints(repository: &dyn TicketRepository) {
+    let header = repository.fetch_header().unwrap();
+    let sprints = repository.fetch_sprints().unwrap();
+    let mut tickets = repository.fetch_tickets().unwrap();
+    tickets.retain(|ticket| ticket.id != "DIG-3");
+    repository.save_tickets(&tickets).unwrap();
+
+    assert_eq!(json(&repository.fetch_header().unwrap()), json(&header));
+    assert_eq!(json(&repository.fetch_sprints().unwrap()), json(&sprints));
+    assert_eq!(
+        ids(&repository.fetch_tickets().unwrap()),
+        ["DIG-2", "DIG-1"]
+    );
+}
+
+fn saving_sprints_keeps_tickets(repository: &dyn TicketRepository) {
+    let tickets = repository.fetch_tickets().unwrap();
+    let mut sprints = repository.fetch_sprints().unwrap();
+    sprints[0].closed = true;
+    let next = sprints[0].following(&sprints);
+    sprints.push(next);
+    repository.save_sprints(&sprints).unwrap();
+
+    let saved = repository.fetch_sprints().unwrap();
+    assert_eq!(saved.len(), 2);
+    assert!(saved[0].closed);
+    assert_eq!(json(&repository.fetch_tickets().unwrap()), json(&tickets));
+}
+
+fn saved_tickets_are_resolved_against_header(repository: &dyn TicketRepository) {
+    let mut tickets = repository.fetch_tickets().unwrap();
+    tickets.push(Ticket::new(
+        "DIG-4".to_string(),
+        TicketLevel::from("XL".to_string()),
+        "Added".to_string(),
+        TicketStatus::from("Review".to_string()),
+    ));
+    repository.save_tickets(&tickets).unwrap();
+
+    let tickets = repository.fetch_tickets().unwrap();
+    let added = find(&tickets, "DIG-4");
+    assert_eq!(added.status.category(), StatusCategory::InProgress);
+    assert_eq!(added.level.points(), 13);
+}
+
+fn saves_an_empty_collection(repository: &dyn TicketRepository) {
+    repository.save_tickets(&[]).unwrap();
+    assert!(repository.fetch_tickets().unwrap().is_empty());
+    assert_eq!(repository.fetch_sprints().unwrap().len(), 1);
+}
+
+macro_rules! conformance_tests {
+    ($open:expr; $($check:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $check() {
+                let seeded = $open;
+                super::$check(seeded.repository.as_ref());
+            }
+        )*
+    };
+}
+
+macro_rules! conformance_suite {
+    ($($backend:ident => $open:expr),* $(,)?) => {
+        $(
+            mod $backend {
+                use super::*;
+
+                conformance_tests!(
+                    $open;
+                    exists,
+                    fetches_tickets_in_stored_order,
+                    resolves_statuses_and_levels_from_header,
+                    fetches_header_and_sprints,
+                    round_trip_preserves_every_field,
+                    saving_tickets_keeps_header_and_sprints,
+                    saving_sprints_keeps_tickets,
+                    saved_tickets_are_resolved_against_header,
+                    saves_an_empty_collection,
+                );
+            }
+        )*
+    };
+}
+
+conformance_suite! {
+    in_memory => in_memory(),
+    toml_file => file(StorageBackend::Toml),
+    json_file => file(StorageBackend::Json),
+    yaml_file => file(StorageBackend::Yaml),
+    sqlite => file(StorageBackend::Sqlite),
+    workspace => workspace(),
+}
@@ -0,0 +1,103 @@
+// TOML ファイルへの書き戻しで、手で整えた並びとコメントが残ること
+use ddomain::repositories::ticket_repository::TicketRepository;
+use dinfrastructure::ticket_repository_impl::TicketRepositoryImpl;
+use std::fs;
+
+mod common;
+use common::TempPath;
+
+const HAND_WRITTEN: &str = r#"# Team backlog
+[header]
+version = 2
+scale = "fibonacci"   # do not change
+
+# --- current work ---
+[[ticket_data]]
+title = "first"       # title first on purpose
+id = "DIG-1"
+level = "Three"
+status = "Pending"
+tags = [ "bug" ]
+created_at = "2026-10-01T00:00:00Z"
+
+# Parked
+[[ticket_data]]
+id = "DIG-2"
+level = "Five"
+title = "second"
+status = "Pending"
+created_at = "2026-10-02T00:00:00Z"
+"#;
+
+fn with_file(name: &str, check: impl FnOnce(&str, &TicketRepositoryImpl)) {
+    let file = TempPath::file(&format!("layout-{}.toml", name), HAND_WRITTEN);
+    check(&file.text(), &TicketRepositoryImpl::new(file.text()));
+}
+
+#[test]
+fn unchanged_tickets_are_written_back_byte_for_byte() {
+    with_file("unchanged", |path, repository| {
+        let tickets = repository.fetch_tickets().unwrap();
+        repository.save_tickets(&tickets).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), HAND_WRITTEN);
+    });
+}
+
+#[test]
+fn only_the_changed_entry_is_rewritten() {
+    with_file("changed", |path, repository| {
+        let mut tickets = repository.fetch_tickets().unwrap();
+        tickets[1].assignee = Some("yu".to_string());
+        repository.save_tickets(&tickets).unwrap();
+
+        let expected = HAND_WRITTEN.replace(
+            "created_at = \"2026-10-02T00:00:00Z\"\n",
+            "created_at = \"2026-10-02T00:00:00Z\"\nassignee = \"yu\"\n",
+        );
+        assert_eq!(fs::read_to_string(path).unwrap(), expected);
+    });
+}
+
+#[test]
+fn reordered_tickets_keep_their_comments() {
+    with_file("reordered", |path, repository| {
+        let mut tickets = repository.fetch_tickets().unwrap();
+        tickets.reverse();
+        repository.save_tickets(&tickets).unwrap();
+
+        let written = fs::read_to_string(path).unwrap();
+        let parked = written
+            .find("# Parked\n[[ticket_data]]\nid = \"DIG-2\"")
+            .unwrap();
+        let current = written.find("# --- current work ---").unwrap();
+        assert!(parked < current);
+        assert!(written.contains("title = \"first\"       # title first on purpose"));
+        assert_eq!(
+            repository
+                .fetch_tickets()
+                .unwrap()
+                .iter()
+                .map(|ticket| ticket.id.as_str())
+                .collect::<Vec<_>>(),
+            ["DIG-2", "DIG-1"]
+        );
+    });
+}
+
+#[test]
+fn sprints_with_native_toml_dates_are_read() {
+    with_file("native-dates", |path, repository| {
+        let sprint =
+            "\n[[sprint_data]]\nname = \"Sprint 1\"\nstart = 2026-10-10\nend = 2026-10-23\n";
+        fs::write(path, format!("{}{}", HAND_WRITTEN, sprint)).unwrap();
+        let mut sprints = repository.fetch_sprints().unwrap();
+        assert_eq!(sprints[0].start.to_string(), "2026-10-10");
+        assert_eq!(sprints[0].end.to_string(), "2026-10-23");
+
+        sprints[0].closed = true;
+        repository.save_sprints(&sprints).unwrap();
+        let saved = repository.fetch_sprints().unwrap();
+        assert!(saved[0].closed);
+        assert_eq!(saved[0].start.to_string(), "2026-10-10");
+    });
+}
@@ -0,0 +1,187 @@
+// 複数のファイルをまとめたワークスペースで、チケットが所属プロジェクトのファイルに保存されること
+use ddomain::entites::ticket::Ticket;
+use ddomain::repositories::ticket_repository::TicketRepository;
+use dinfrastructure::ticket_repository_impl::TicketRepositoryImpl;
+use dinfrastructure::workspace_repository::WorkspaceRepository;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::TempPath;
+
+const ALPHA: &str = r#"
+[[ticket_data]]
+id = "A-1"
+level = "S"
+title = "Stays"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+
+[[ticket_data]]
+id = "A-2"
+level = "S"
+title = "Moves"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+"#;
+
+const BETA: &str = r#"
+[[ticket_data]]
+id = "B-1"
+level = "M"
+title = "Already there"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+"#;
+
+// alpha.toml と beta.toml を置いたディレクトリ
+fn workspace_dir(name: &str) -> TempPath {
+    let root = TempPath::dir(&format!("workspace-{}", name));
+    root.write("alpha.toml", ALPHA);
+    root.write("beta.toml", BETA);
+    root
+}
+
+fn open(root: &Path) -> WorkspaceRepository {
+    WorkspaceRepository::new(
+        ["alpha.toml", "beta.toml"]
+            .iter()
+            .map(|name| TicketRepositoryImpl::new(root.join(name).to_string_lossy().to_string()))
+            .collect(),
+    )
+}
+
+fn ids_in(root: &Path, name: &str) -> Vec<String> {
+    TicketRepositoryImpl::new(root.join(name).to_string_lossy().to_string())
+        .fetch_tickets()
+        .unwrap()
+        .into_iter()
+        .map(|ticket| ticket.id)
+        .collect()
+}
+
+fn move_to(tickets: &mut [Ticket], id: &str, project: &str) {
+    let ticket = tickets.iter_mut().find(|ticket| ticket.id == id).unwrap();
+    ticket.project = Some(project.to_string());
+}
+
+#[test]
+fn a_ticket_moved_to_another_project_is_saved_in_that_file() {
+    let root = workspace_dir("move");
+    let workspace = open(root.path());
+    let mut tickets = workspace.fetch_tickets().unwrap();
+    move_to(&mut tickets, "A-2", "beta");
+    workspace.save_tickets(&tickets).unwrap();
+
+    let (alpha, beta) = (
+        ids_in(root.path(), "alpha.toml"),
+        ids_in(root.path(), "beta.toml"),
+    );
+    let reopened = open(root.path()).fetch_tickets().unwrap();
+
+    assert_eq!(alpha, ["A-1"]);
+    assert_eq!(beta, ["A-2", "B-1"]);
+    let moved = reopened.iter().find(|ticket| ticket.id == "A-2").unwrap();
+    assert_eq!(moved.project.as_deref(), Some("beta"));
+    assert_eq!(moved.title, "Moves");
+}
+
+#[test]
+fn a_moved_ticket_is_kept_when_the_target_file_cannot_be_written() {
+    let root = workspace_dir("unwritable");
+    let workspace = open(root.path());
+    let mut tickets = workspace.fetch_tickets().unwrap();
+    move_to(&mut tickets, "A-2", "beta");
+    // 保存先のファイルが読み書きできなくなった場合
+    fs::remove_file(root.path().join("beta.toml")).unwrap();
+    fs::create_dir(root.path().join("beta.toml")).unwrap();
+    let saved = workspace.save_tickets(&tickets);
+
+    let alpha = ids_in(root.path(), "alpha.toml");
+
+    assert!(saved.is_err());
+    assert_eq!(alpha, ["A-1", "A-2"]);
+}
+
+// どちらも DIG-1 から番号を振った2つのファイル
+const FIRST: &str = r#"
+[[ticket_data]]
+id = "DIG-1"
+level = "S"
+title = "First project"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+"#;
+
+const SECOND: &str = r#"
+[[ticket_data]]
+id = "DIG-1"
+level = "M"
+title = "Second project"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+
+[[ticket_data]]
+id = "DIG-2"
+level = "S"
+title = "Child in the second project"
+status = "Pending"
+created_at = "2026-10-01T09:00:00Z"
+parent = "DIG-1"
+blocked_by = ["DIG-1"]
+"#;
+
+fn numbered_from_one(name: &str) -> (TempPath, WorkspaceRepository) {
+    let root = workspace_dir(name);
+    root.write("alpha.toml", FIRST);
+    root.write("beta.toml", SECOND);
+    let workspace = open(root.path());
+    (root, workspace)
+}
+
+#[test]
+fn files_numbered_from_the_same_id_open_together() {
+    let (root, workspace) = numbered_from_one("same-ids");
+    let tickets = workspace.fetch_tickets().unwrap();
+    workspace.save_tickets(&tickets).unwrap();
+    let (alpha, beta) = (
+        ids_in(root.path(), "alpha.toml"),
+        ids_in(root.path(), "beta.toml"),
+    );
+
+    let ids: Vec<&str> = tickets.iter().map(|ticket| ticket.id.as_str()).collect();
+    assert_eq!(ids, ["alpha/DIG-1", "beta/DIG-1", "beta/DIG-2"]);
+    assert_eq!(tickets[2].parent.as_deref(), Some("beta/DIG-1"));
+    assert_eq!(tickets[2].blocked_by, ["beta/DIG-1"]);
+    // ファイルには元のIDのまま書き戻す
+    assert_eq!(alpha, ["DIG-1"]);
+    assert_eq!(beta, ["DIG-1", "DIG-2"]);
+}
+
+#[test]
+fn a_moved_ticket_is_renumbered_only_when_its_id_is_taken() {
+    let (root, workspace) = numbered_from_one("renumber");
+    let mut tickets = workspace.fetch_tickets().unwrap();
+    move_to(&mut tickets, "beta/DIG-1", "alpha");
+    workspace.save_tickets(&tickets).unwrap();
+
+    let (alpha, beta) = (
+        ids_in(root.path(), "alpha.toml"),
+        ids_in(root.path(), "beta.toml"),
+    );
+    let reopened = open(root.path()).fetch_tickets().unwrap();
+
+    assert_eq!(alpha, ["DIG-1", "DIG-2"]);
+    assert_eq!(beta, ["DIG-2"]);
+    // 移したチケットへの参照は移動先のIDを指す
+    let child = reopened
+        .iter()
+        .find(|ticket| ticket.title == "Child in the second project")
+        .unwrap();
+    let parent = reopened
+        .iter()
+        .find(|ticket| Some(&ticket.id) == child.parent.as_ref())
+        .unwrap();
+    assert_eq!(parent.title, "Second project");
+    assert_eq!(child.blocked_by, [parent.id.as_str()]);
+}
@@ -1 +1,2 @@
+pub mod http_controller;
 pub mod terminal_controller;
@@ -0,0 +1,245 @@
+use color_eyre::eyre::{eyre, Report};
+use color_eyre::Result;
+use dapplication::dtos::api_dto::{TicketRequestDTO, TransitionRequestDTO};
+use dapplication::input_ports::api_input_port::ApiInputPort;
+use dapplication::parsers::query_parser::parse_query;
+use ddomain::domain_errors::DomainError;
+use ddomain::value_objects::ticket_filter::TicketFilter;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::Cursor;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+type HttpResponse = Response<Cursor<Vec<u8>>>;
+
+// ルートごとのユースケースの呼び出しと、結果の HTTP の応答への変換
+//   GET    /tickets?tag=&assignee=&query=   一覧
+//   POST   /tickets                         作成
+//   GET    /tickets/{id}                    取得(ETag を返す)
+//   PATCH  /tickets/{id}                    更新(If-Match が必要)
+//   POST   /tickets/{id}/transition         ステータスの移行(If-Match が必要)
+//   DELETE /tickets/{id}                    削除(If-Match が必要)
+pub struct HttpController<T: ApiInputPort> {
+    input_port: T,
+}
+
+// 値は ASCII に限られるため、ID などを含める場合は encode を通す
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+// URL のパスの一部として使えない文字を "%XX" にする
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn json<B: Serialize>(status: u16, body: &B) -> HttpResponse {
+    let body = serde_json::to_vec_pretty(body).unwrap_or_default();
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn error(status: u16, message: &str) -> HttpResponse {
+    json(status, &serde_json::json!({ "error": message }))
+}
+
+// ETag は引用符で囲む
+fn with_etag(response: HttpResponse, etag: &str) -> HttpResponse {
+    response.with_header(header("ETag", &format!("\"{}\"", etag)))
+}
+
+// ユースケースのエラーの種類に応じたステータスコード
+fn error_status(report: &Report) -> u16 {
+    match report.downcast_ref::<DomainError>() {
+        Some(DomainError::TicketNotFound(_)) => 404,
+        Some(DomainError::StaleTicket(_)) => 412,
+        Some(
+            DomainError::TransitionNotAllowed(..)
+            | DomainError::Blocked(..)
+            | DomainError::OpenChildren(_)
+            | DomainError::HierarchyCycle(_)
+            | DomainError::DependencyCycle(_),
+        ) => 409,
+        Some(
+            DomainError::FileRead(_)
+            | DomainError::FileWrite(_)
+            | DomainError::TomlParse(_)
+            | DomainError::TomlSerialize(_)
+            | DomainError::DocumentParse(..)
+            | DomainError::DocumentSerialize(..)
+            | DomainError::Database(_)
+            | DomainError::UnsupportedVersion(..)
+            | DomainError::EmptyFile
+            | DomainError::FileNotFound(_)
+            | DomainError::DuplicateTicket(..),
+        ) => 500,
+        // 値の誤りなど、リクエストの内容を処理できない場合
+        _ => 422,
+    }
+}
+
+// "%20" を戻す。クエリの値では "+" も空白に戻す
+fn decode(text: &str, plus_as_space: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' if plus_as_space => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+// 一覧の絞り込み条件。tag は繰り返し指定できる
+fn filter(query: &str) -> Result<TicketFilter> {
+    let mut filter = TicketFilter::default();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = decode(value, true);
+        match key {
+            "tag" => filter.tags.push(value),
+            "assignee" => filter.assignee = Some(value).filter(|value| !value.is_empty()),
+            "query" => filter.conditions = parse_query(&value).map_err(|e| eyre!(e))?,
+            _ => return Err(eyre!("Unknown query parameter '{}'.", key)),
+        }
+    }
+    Ok(filter)
+}
+
+fn body<B: DeserializeOwned>(request: &mut Request) -> Result<B, HttpResponse> {
+    let mut content = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut content)
+        .map_err(|e| error(400, &e.to_string()))?;
+    serde_json::from_str(&content).map_err(|e| error(400, &format!("Invalid JSON: {}", e)))
+}
+
+// 変更には取得時の ETag を If-Match で指定する必要がある
+fn if_match(request: &Request) -> Result<String, HttpResponse> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("If-Match"))
+        .map(|header| {
+            let value = header.value.as_str().trim();
+            value.trim_start_matches("W/").trim_matches('"').to_string()
+        })
+        .ok_or_else(|| {
+            error(
+                428,
+                "Send the ETag of the ticket in If-Match (or * to skip the check).",
+            )
+        })
+}
+
+fn if_none_match(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("If-None-Match"))
+        .map(|header| header.value.as_str().trim().trim_matches('"').to_string())
+}
+
+impl<T: ApiInputPort> HttpController<T> {
+    pub fn new(input_port: T) -> Self {
+        HttpController { input_port }
+    }
+
+    // リクエストは1件ずつ順に処理する(ファイルへの書き込みが重ならないように)
+    pub fn run(self, address: &str) -> Result<()> {
+        let server =
+            Server::http(address).map_err(|e| eyre!("Could not listen on {}: {}", address, e))?;
+        for mut request in server.incoming_requests() {
+            let response = self
+                .handle(&mut request)
+                .unwrap_or_else(|response| response);
+            // 接続が切れた場合などは次のリクエストに進む
+            let _ = request.respond(response);
+        }
+        Ok(())
+    }
+
+    fn handle(&self, request: &mut Request) -> Result<HttpResponse, HttpResponse> {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| decode(segment, false))
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let failed = |report: Report| error(error_status(&report), &report.to_string());
+
+        match (request.method(), segments.as_slice()) {
+            (Method::Get, ["tickets"]) => {
+                let filter = filter(query).map_err(|report| error(400, &report.to_string()))?;
+                let tickets = self.input_port.list(filter).map_err(failed)?;
+                Ok(json(200, &tickets))
+            }
+            (Method::Post, ["tickets"]) => {
+                let request: TicketRequestDTO = body(request)?;
+                let ticket = self.input_port.create(request).map_err(failed)?;
+                let location = format!("/tickets/{}", encode(&ticket.ticket.id));
+                Ok(with_etag(json(201, &ticket), &ticket.etag)
+                    .with_header(header("Location", &location)))
+            }
+            (Method::Get, ["tickets", id]) => {
+                let ticket = self.input_port.get(id).map_err(failed)?;
+                if if_none_match(request).as_deref() == Some(ticket.etag.as_str()) {
+                    return Ok(with_etag(Response::from_data(Vec::new()), &ticket.etag)
+                        .with_status_code(304));
+                }
+                Ok(with_etag(json(200, &ticket), &ticket.etag))
+            }
+            (Method::Patch, ["tickets", id]) => {
+                let expected = if_match(request)?;
+                let update: TicketRequestDTO = body(request)?;
+                let ticket = self
+                    .input_port
+                    .update(id, &expected, update)
+                    .map_err(failed)?;
+                Ok(with_etag(json(200, &ticket), &ticket.etag))
+            }
+            (Method::Post, ["tickets", id, "transition"]) => {
+                let expected = if_match(request)?;
+                let transition: TransitionRequestDTO = body(request)?;
+                let ticket = self
+                    .input_port
+                    .transition(id, &expected, transition)
+                    .map_err(failed)?;
+                Ok(with_etag(json(200, &ticket), &ticket.etag))
+            }
+            (Method::Delete, ["tickets", id]) => {
+                let expected = if_match(request)?;
+                self.input_port.delete(id, &expected).map_err(failed)?;
+                Ok(Response::from_data(Vec::new()).with_status_code(204))
+            }
+            (_, ["tickets"] | ["tickets", _] | ["tickets", _, "transition"]) => {
+                Err(error(405, "This method is not allowed here."))
+            }
+            _ => Err(error(404, "Not found.")),
+        }
+    }
+}
@@ -26,10 +26,48 @@ impl<T: TerminalInputPort> TerminalController<T> {
 
     fn handle_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
+            if key.kind == KeyEventKind::Press && self.input_port.is_input_mode() {
+                // 入力欄の編集中は文字入力として扱う
+                match key.code {
+                    KeyCode::Enter => self.input_port.submit_input()?,
+                    KeyCode::Esc => self.input_port.cancel_input()?,
+                    KeyCode::Backspace => self.input_port.input_backspace()?,
+                    KeyCode::Char(c) => self.input_port.input_char(c)?,
+                    _ => {}
+                }
+            } else if key.kind == KeyEventKind::Press {
                 match key.code {
                     KeyCode::Char('q') | KeyCode::Esc => return Ok(true), // 終了
                     KeyCode::Char('l') => self.input_port.enter_edit_mode()?,
+                    KeyCode::Char('h') => self.input_port.enter_normal_mode()?,
+                    KeyCode::Char('c') => self.input_port.enter_chart_mode()?,
+                    KeyCode::Char('w') => self.input_port.cycle_chart_window()?,
+                    KeyCode::Char('t') => self.input_port.cycle_status(false)?,
+                    KeyCode::Char('T') => self.input_port.cycle_status(true)?,
+                    KeyCode::Char('z') => self.input_port.toggle_collapse()?,
+                    KeyCode::Char('C') => self.input_port.add_comment()?,
+                    KeyCode::Char('n') => self.input_port.next_field()?,
+                    KeyCode::Char('e') => self.input_port.edit_field()?,
+                    KeyCode::Char('g') => self.input_port.edit_tags()?,
+                    KeyCode::Char('f') => self.input_port.edit_tag_filter()?,
+                    KeyCode::Char('a') => self.input_port.cycle_assignee()?,
+                    KeyCode::Char('v') => self.input_port.cycle_estimate()?,
+                    KeyCode::Char('P') => self.input_port.cycle_priority()?,
+                    KeyCode::Char('K') => self.input_port.move_rank(true)?,
+                    KeyCode::Char('J') => self.input_port.move_rank(false)?,
+                    KeyCode::Char('m') => self.input_port.toggle_mine_filter()?,
+                    KeyCode::Char('d') => self.input_port.edit_due_date()?,
+                    KeyCode::Char('A') => self.input_port.enter_agenda_mode()?,
+                    KeyCode::Char('b') => self.input_port.enter_board_mode()?,
+                    KeyCode::Char('i') => self.input_port.toggle_timer()?,
+                    KeyCode::Char('R') => self.input_port.edit_recurrence()?,
+                    KeyCode::Char('L') => self.input_port.log_time()?,
+                    KeyCode::Char('s') => self.input_port.toggle_sprint_filter()?,
+                    KeyCode::Char('p') => self.input_port.cycle_ticket_sprint()?,
+                    KeyCode::Char('X') => self.input_port.close_current_sprint()?,
+                    KeyCode::Char('o') => self.input_port.cycle_project()?,
+                    KeyCode::Char('F') => self.input_port.resolve_fixed()?,
+                    KeyCode::Char('B') => self.input_port.create_branch()?,
                     KeyCode::Char('j') | KeyCode::Down => self.input_port.next_row()?,
                     KeyCode::Char('k') | KeyCode::Up => self.input_port.previous_row()?,
                     _ => {}
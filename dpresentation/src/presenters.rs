@@ -1 +1,2 @@
+pub mod console_presenter;
 pub mod ratatui_presenter;
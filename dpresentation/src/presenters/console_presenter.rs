@@ -0,0 +1,152 @@
+use dapplication::{
+    dtos::{
+        graph_dto::{GraphDTO, GraphFormat},
+        ticket_dto::TicketDTO,
+        timesheet_dto::TimesheetDTO,
+        workload_dto::WorkloadDTO,
+    },
+    output_ports::cli_output_port::CliOutputPort,
+};
+
+#[derive(Default)]
+pub struct ConsolePresenter;
+
+impl ConsolePresenter {
+    pub fn new() -> Self {
+        ConsolePresenter
+    }
+}
+
+fn escape_label(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// MermaidのノードIDはノードの位置から作る。チケットのIDは記号を含んでも重ならないようラベルに表示する
+fn mermaid_id(graph: &GraphDTO, id: &str) -> Option<String> {
+    graph
+        .nodes
+        .iter()
+        .position(|node| node.id == id)
+        .map(|index| format!("n{}", index))
+}
+
+// ステータスの分類ごとの塗りつぶし色
+fn dot_color(category: &str) -> &'static str {
+    match category {
+        "in-progress" => "lightblue",
+        "done" => "palegreen",
+        "canceled" => "lightgray",
+        _ => "white",
+    }
+}
+
+impl CliOutputPort for ConsolePresenter {
+    fn present_tickets(&self, tickets: &[TicketDTO]) {
+        for ticket in tickets {
+            let tags: Vec<String> = ticket
+                .tags
+                .iter()
+                .map(|tag| format!("#{}", tag.name))
+                .collect();
+            println!(
+                "{:<10} {:<9} {:<8} {:<9} {:<10} {} {}",
+                ticket.id,
+                ticket.status,
+                ticket.priority,
+                ticket.level,
+                ticket.assignee,
+                ticket.title,
+                tags.join(" ")
+            );
+        }
+    }
+
+    fn present_warnings(&self, warnings: &[String]) {
+        for warning in warnings {
+            eprintln!("warning: {}", warning);
+        }
+    }
+
+    fn present_workload(&self, workload: &[WorkloadDTO]) {
+        println!(
+            "{:<12} {:<20} {:>6} {:>7}",
+            "Assignee", "Name", "Points", "Tickets"
+        );
+        for entry in workload {
+            let assignee = if entry.assignee.is_empty() {
+                "(none)"
+            } else {
+                entry.assignee.as_str()
+            };
+            println!(
+                "{:<12} {:<20} {:>6} {:>7}",
+                assignee, entry.name, entry.open_points, entry.open_tickets
+            );
+        }
+    }
+
+    fn present_timesheet(&self, timesheet: &TimesheetDTO) {
+        println!(
+            "Timesheet {} .. {} (by {})",
+            timesheet.from, timesheet.to, timesheet.grouping
+        );
+        for row in &timesheet.rows {
+            println!("{:<20} {:>8.2}h", row.key, row.hours);
+        }
+        println!("{:<20} {:>8.2}h", "Total", timesheet.total_hours);
+    }
+
+    fn present_graph(&self, graph: &GraphDTO, format: GraphFormat) {
+        match format {
+            GraphFormat::Dot => {
+                println!("digraph dependencies {{");
+                println!("    rankdir=LR;");
+                println!("    node [shape=box, style=filled];");
+                for node in &graph.nodes {
+                    println!(
+                        "    \"{}\" [label=\"{}\\n{}\", fillcolor={}];",
+                        escape_label(&node.id),
+                        escape_label(&node.id),
+                        escape_label(&node.title),
+                        dot_color(&node.category)
+                    );
+                }
+                for (blocker, blocked) in &graph.edges {
+                    println!(
+                        "    \"{}\" -> \"{}\";",
+                        escape_label(blocker),
+                        escape_label(blocked)
+                    );
+                }
+                println!("}}");
+            }
+            GraphFormat::Mermaid => {
+                println!("graph LR");
+                for (index, node) in graph.nodes.iter().enumerate() {
+                    println!(
+                        "    n{}[\"{}: {} ({})\"]",
+                        index,
+                        node.id.replace('"', "#quot;"),
+                        node.title.replace('"', "#quot;"),
+                        node.status
+                    );
+                }
+                for (blocker, blocked) in &graph.edges {
+                    if let (Some(blocker), Some(blocked)) =
+                        (mermaid_id(graph, blocker), mermaid_id(graph, blocked))
+                    {
+                        println!("    {} --> {}", blocker, blocked);
+                    }
+                }
+            }
+        }
+    }
+
+    fn present_message(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn present_export(&self, content: &str) {
+        print!("{}", content);
+    }
+}
@@ -1,13 +1,26 @@
 use dapplication::{
-    dtos::ticket_dto::TicketDTO, output_ports::terminal_output_port::TerminalOutputPort,
+    dtos::{
+        agenda_dto::AgendaGroupDTO,
+        board_dto::BoardColumnDTO,
+        chart_dto::ChartDTO,
+        ticket_dto::{FieldDTO, TagDTO, TicketDTO},
+    },
+    output_ports::terminal_output_port::TerminalOutputPort,
 };
+use ddomain::value_objects::app_mode::AppMode;
 use ratatui::{
-    layout::{Constraint, Rect},
-    style::{Color, Style},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph,
+        Row, Table, Wrap,
+    },
     Frame,
 };
 
+#[derive(Default)]
 pub struct RatatuiPresenter;
 
 impl RatatuiPresenter {
@@ -16,94 +29,585 @@ impl RatatuiPresenter {
     }
 }
 
+// タグをチップ状に描画する。未定義のタグは赤字に「?」を付けて警告する
+fn tag_chips(tags: &[TagDTO]) -> Line<'static> {
+    let mut spans = Vec::new();
+    for tag in tags {
+        let span = if tag.registered {
+            let color = tag.color.parse::<Color>().unwrap_or(Color::Gray);
+            Span::styled(
+                format!(" {} ", tag.name),
+                Style::default().fg(Color::Black).bg(color),
+            )
+        } else {
+            Span::styled(format!(" {}? ", tag.name), Style::default().fg(Color::Red))
+        };
+        spans.push(span);
+        spans.push(Span::raw(" "));
+    }
+    Line::from(spans)
+}
+
+// 優先度ごとの表の印と色
+fn priority_marker(priority: &str) -> Span<'static> {
+    let (marker, color) = match priority {
+        "Critical" => ("!!", Color::LightRed),
+        "High" => ("↑", Color::LightYellow),
+        "Low" => ("↓", Color::Gray),
+        _ => ("-", Color::LightBlue),
+    };
+    Span::styled(marker, Style::default().fg(color))
+}
+
+// ステータスの分類ごとの色
+fn category_color(category: &str) -> Color {
+    match category {
+        "in-progress" => Color::Cyan,
+        "done" => Color::Green,
+        "canceled" => Color::DarkGray,
+        _ => Color::Gray,
+    }
+}
+
+// カスタムフィールドを型に応じた部品として描画する
+fn field_widget(field: &FieldDTO) -> Vec<Span<'static>> {
+    if !field.valid || field.kind.is_empty() {
+        return vec![Span::styled(
+            format!("{} ?", field.value),
+            Style::default().fg(Color::Red),
+        )];
+    }
+    match field.kind.as_str() {
+        "bool" => vec![Span::raw(if field.value == "true" { "[x]" } else { "[ ]" })],
+        "enum" => field
+            .options
+            .iter()
+            .map(|option| {
+                if *option == field.value {
+                    Span::styled(
+                        format!(" {} ", option),
+                        Style::default().fg(Color::Black).bg(Color::Cyan),
+                    )
+                } else {
+                    Span::styled(
+                        format!(" {} ", option),
+                        Style::default().fg(Color::DarkGray),
+                    )
+                }
+            })
+            .collect(),
+        "date" if field.value.is_empty() => vec![Span::styled(
+            "[YYYY-MM-DD]",
+            Style::default().fg(Color::DarkGray),
+        )],
+        _ => vec![Span::styled(
+            format!("[{:<16}]", field.value),
+            Style::default().add_modifier(Modifier::UNDERLINED),
+        )],
+    }
+}
+
 impl TerminalOutputPort for RatatuiPresenter {
     fn draw_table(
         &self,
         frame: &mut Frame,
         area: Rect,
+        title: &str,
         selected_index: Option<usize>,
-        tickets: &Vec<TicketDTO>,
+        tickets: &[TicketDTO],
     ) {
+        // 複数のファイルを開いている場合は ID の後にプロジェクトの列を加える
+        let show_project = tickets.iter().any(|ticket| !ticket.project.is_empty());
         let header_style = Style::default().fg(Color::White).bg(Color::Blue);
-        let header = Row::new(
-            [
-                "ID",
-                "Level",
-                "Title",
-                "Status",
-                "Created At",
-                "Resolved At",
-            ]
-            .iter()
-            .map(|&s| Cell::from(s)),
-        )
-        .style(header_style)
-        .height(1);
+        let mut titles = vec![
+            "P",
+            "ID",
+            "Level",
+            "Points",
+            "Title",
+            "Tags",
+            "Status",
+            "Assignee",
+            "Due",
+            "Time",
+            "Deps",
+            "Created At",
+            "Resolved At",
+            "Sprint",
+        ];
+        if show_project {
+            titles.insert(2, "Project");
+        }
+        let header = Row::new(titles.into_iter().map(Cell::from))
+            .style(header_style)
+            .height(1);
 
         let rows: Vec<Row> = tickets
             .iter()
             .enumerate()
             .map(|(i, ticket)| {
+                // 期限切れは赤、期日が近いものは黄色の文字で強調する
                 let row_style = if selected_index == Some(i) {
                     Style::default().fg(Color::Black).bg(Color::Yellow)
                 } else {
-                    Style::default().fg(Color::White).bg(Color::DarkGray)
+                    match ticket.due_bucket.as_str() {
+                        "Overdue" => Style::default().fg(Color::White).bg(Color::Red),
+                        "Today" | "This week" => {
+                            Style::default().fg(Color::Yellow).bg(Color::DarkGray)
+                        }
+                        _ => Style::default().fg(Color::White).bg(Color::DarkGray),
+                    }
                 };
 
-                Row::new([
+                // 階層に応じてインデントし、子を持つチケットには折りたたみ状態を表示
+                let marker = match (ticket.has_children, ticket.collapsed) {
+                    (true, true) => "▸ ",
+                    (true, false) => "▾ ",
+                    (false, _) if ticket.depth > 0 => "└ ",
+                    (false, _) => "",
+                };
+                let title = format!("{}{}{}", "  ".repeat(ticket.depth), marker, ticket.title);
+
+                // ⊘: 未完了のブロッカー数, →: ブロックしているチケット数
+                let mut deps = Vec::new();
+                if ticket.open_blockers > 0 {
+                    deps.push(format!("⊘{}", ticket.open_blockers));
+                }
+                if ticket.blocking > 0 {
+                    deps.push(format!("→{}", ticket.blocking));
+                }
+
+                let mut cells = vec![
+                    Cell::from(priority_marker(&ticket.priority)),
                     Cell::from(ticket.id.as_str()),
                     Cell::from(ticket.level.as_str()),
-                    Cell::from(ticket.title.as_str()),
-                    Cell::from(ticket.status.as_str()),
-                    Cell::from(ticket.created_at.to_rfc3339()), // Created At
+                    Cell::from(ticket.points.to_string()),
+                    Cell::from(title),
+                    Cell::from(tag_chips(&ticket.tags)),
+                    Cell::from(Span::styled(
+                        ticket.status.as_str(),
+                        Style::default().fg(category_color(&ticket.status_category)),
+                    )),
+                    Cell::from(ticket.assignee.as_str()),
+                    // 繰り返しのチケットには ↻ を付ける
+                    Cell::from(if ticket.recurrence.is_empty() {
+                        ticket.due.clone()
+                    } else {
+                        format!("{} ↻", ticket.due)
+                    }),
+                    // 計測中のタイマーがある場合は ● を付ける
+                    Cell::from(if ticket.timer_running {
+                        format!("{} ●", ticket.tracked)
+                    } else {
+                        ticket.tracked.clone()
+                    }),
+                    Cell::from(deps.join(" ")),
+                    Cell::from(ticket.created_at.format("%Y-%m-%d %H:%M").to_string()), // Created At
                     Cell::from(
                         ticket.resolved_at
-                        .map(|dt| dt.to_rfc3339()) // Resolved AtがSomeなら日付を表示
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string()) // Resolved AtがSomeなら日付を表示
                         .unwrap_or_else(|| "".to_string()), // Noneなら空文字列
                     ),
-                ])
-                .style(row_style)
+                    Cell::from(ticket.sprint.as_str()),
+                ];
+                if show_project {
+                    cells.insert(2, Cell::from(ticket.project.as_str()));
+                }
+                Row::new(cells).style(row_style)
             })
             .collect();
 
-        let widths = vec![
+        let mut widths = vec![
+            Constraint::Length(2),  // Width of Priority column
             Constraint::Length(10), // Width of ID column
             Constraint::Length(10), // Width of Level column
+            Constraint::Length(6),  // Width of Points column
             Constraint::Length(30), // Width of Title column
+            Constraint::Length(20), // Width of Tags column
             Constraint::Length(15), // Width of Status column
-            Constraint::Length(25), // Width of Created At column
-            Constraint::Length(25), // Width of Resolved At column
+            Constraint::Length(12), // Width of Assignee column
+            Constraint::Length(12), // Width of Due column
+            Constraint::Length(9),  // Width of Time column
+            Constraint::Length(8),  // Width of Deps column
+            Constraint::Length(17), // Width of Created At column
+            Constraint::Length(17), // Width of Resolved At column
+            Constraint::Length(15), // Width of Sprint column
         ];
+        if show_project {
+            widths.insert(2, Constraint::Length(12)); // Width of Project column
+        }
 
         frame.render_widget(
             Table::new(std::iter::once(header).chain(rows), &widths)
-                .block(Block::default().borders(Borders::ALL).title("Ticket List")),
+                .block(Block::default().borders(Borders::ALL).title(title)),
             area,
         );
     }
 
-    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
-        let footer_text = "(q) Exit | (k) Up | (j) Down | (l) Edit Mode";
+    fn draw_footer(&self, frame: &mut Frame, area: Rect, mode: &AppMode, notice: Option<&str>) {
+        let guide = match mode {
+            AppMode::Chart => "(q) Exit | (w) Change Window | (h) Back",
+            AppMode::Agenda | AppMode::Board => "(q) Exit | (h) Back",
+            AppMode::Edit => "(q) Exit | (C) Comment | (t/T) Next Status | (g) Tags | (a) Assign | (v) Estimate | (P) Priority | (d) Due | (i) Timer | (L) Log Time | (B) Branch | (h) Back",
+            _ => "(q) Exit | (k) Up | (j) Down | (l) Detail | (t/T) Next Status (T: force) | (z) Fold | (g) Tags | (f) Filter | (a) Assign | (v) Estimate | (P) Priority | (J/K) Rank | (m) Mine | (d) Due | (R) Repeat | (A) Agenda | (b) Board | (i) Timer | (L) Log Time | (c) Charts | (s) Sprint Filter | (p) Assign Sprint | (X) Close Sprint | (o) Move Project | (B) Branch | (F) Resolve Fixed",
+        };
+        // 通知がある場合は枠のタイトルに強調して表示する
+        let title = match notice {
+            Some(notice) => Line::styled(
+                format!("Control Guide - {}", notice),
+                Style::default().fg(Color::Yellow),
+            ),
+            None => Line::from("Control Guide"),
+        };
         frame.render_widget(
-            Paragraph::new(footer_text)
+            Paragraph::new(guide)
                 .style(Style::default().fg(Color::White))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            area,
+        );
+    }
+
+    fn draw_detail(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        selected_ticket: Option<&TicketDTO>,
+        selected_field: usize,
+    ) {
+        let Some(ticket) = selected_ticket else {
+            frame.render_widget(
+                Paragraph::new("No ticket selected.").block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Ticket Detail"),
+                ),
+                area,
+            );
+            return;
+        };
+
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{:<11}", name), Style::default().fg(Color::Gray)),
+                Span::raw(value),
+            ])
+        };
+        let mut tags = tag_chips(&ticket.tags);
+        tags.spans.insert(
+            0,
+            Span::styled(format!("{:<11}", "Tags"), Style::default().fg(Color::Gray)),
+        );
+        let mut lines = vec![
+            Line::styled(
+                format!("{} {}", ticket.id, ticket.title),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(""),
+            field("Level", format!("{} ({} pt)", ticket.level, ticket.points)),
+            field("Status", ticket.status.clone()),
+            field("Priority", ticket.priority.clone()),
+            field("Assignee", ticket.assignee.clone()),
+            field("Sprint", ticket.sprint.clone()),
+            field("Parent", ticket.parent.clone()),
+            field("Blocked by", ticket.blocked_by.join(", ")),
+            tags,
+            field("Due", ticket.due.clone()),
+            field("Repeat", ticket.recurrence.clone()),
+            field("Time", ticket.tracked.clone()),
+            field(
+                "Created",
+                ticket.created_at.format("%Y-%m-%d %H:%M").to_string(),
+            ),
+            field(
+                "Resolved",
+                ticket
+                    .resolved_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            ),
+        ];
+        // 複数のファイルを開いている場合は所属プロジェクトも表示する
+        if !ticket.project.is_empty() {
+            lines.insert(6, field("Project", ticket.project.clone()));
+        }
+        // digger scan で作ったチケットはコメントの位置を表示する
+        if !ticket.source.is_empty() {
+            lines.push(field("Source", ticket.source.clone()));
+        }
+        if !ticket.fields.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                "Fields (n) Next | (e) Edit",
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            for (i, field) in ticket.fields.iter().enumerate() {
+                let marker = if i == selected_field { "▶ " } else { "  " };
+                let mut spans = vec![Span::styled(
+                    format!("{}{:<16}", marker, field.name),
+                    Style::default().fg(Color::Gray),
+                )];
+                spans.extend(field_widget(field));
+                lines.push(Line::from(spans));
+            }
+        }
+        // メッセージでこのチケットに触れているコミット。完了を示すものには ✔ を付ける
+        if !ticket.commits.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                format!("Commits ({})", ticket.commits.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            for commit in &ticket.commits {
+                let mut spans = vec![
+                    Span::styled(
+                        commit.short_hash().to_string(),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(
+                        format!(" {} {} ", commit.date.format("%Y-%m-%d"), commit.author),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::raw(commit.summary().to_string()),
+                ];
+                if commit.closes {
+                    spans.push(Span::styled(" ✔", Style::default().fg(Color::Green)));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+        lines.extend([
+            Line::from(""),
+            Line::styled(
+                format!("Comments ({})", ticket.comments.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        for comment in &ticket.comments {
+            lines.push(Line::styled(
+                format!(
+                    "{} {}",
+                    comment.created_at.format("%Y-%m-%d %H:%M"),
+                    comment.author
+                ),
+                Style::default().fg(Color::Cyan),
+            ));
+            lines.extend(
+                comment
+                    .body
+                    .lines()
+                    .map(|line| Line::from(format!("  {}", line))),
+            );
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Ticket Detail"),
+            ),
+            area,
+        );
+    }
+
+    fn draw_input(&self, frame: &mut Frame, area: Rect, prompt: &str, value: &str) {
+        frame.render_widget(
+            Paragraph::new(format!("{}_", value))
+                .style(Style::default().fg(Color::Yellow))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title("Control Guide"),
+                        .title(format!("{} - (Enter) Save | (Esc) Cancel", prompt)),
                 ),
             area,
         );
     }
 
-    fn draw_edit_form(&self, frame: &mut Frame, area: Rect, selected_ticket: Option<&str>) {
-        let form_text = match selected_ticket {
-            Some(ticket) => format!("Selected Ticket: {}", ticket),
-            None => "Edit Mode: No ticket selected.".to_string(),
+    fn draw_agenda(&self, frame: &mut Frame, area: Rect, agenda: &[AgendaGroupDTO]) {
+        let mut lines = Vec::new();
+        for group in agenda {
+            let header_style = match group.title.as_str() {
+                "Overdue" => Style::default().fg(Color::Red),
+                "Today" => Style::default().fg(Color::Yellow),
+                _ => Style::default().fg(Color::Cyan),
+            };
+            lines.push(Line::styled(
+                format!("{} ({})", group.title, group.tickets.len()),
+                header_style.add_modifier(Modifier::BOLD),
+            ));
+            for ticket in &group.tickets {
+                lines.push(Line::from(format!(
+                    "  {}  {:<10} {:<9} {} {}",
+                    ticket.due, ticket.id, ticket.status, ticket.title, ticket.assignee
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Agenda")),
+            area,
+        );
+    }
+
+    fn draw_board(&self, frame: &mut Frame, area: Rect, title: &str, columns: &[BoardColumnDTO]) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title.to_string());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if columns.is_empty() {
+            return;
+        }
+
+        let rects = Layout::horizontal(vec![
+            Constraint::Ratio(1, columns.len() as u32);
+            columns.len()
+        ])
+        .split(inner);
+        for (column, rect) in columns.iter().zip(rects.iter()) {
+            let color = category_color(&column.category);
+            let mut lines = Vec::new();
+            for ticket in &column.tickets {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", ticket.id),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(ticket.title.clone()),
+                ]));
+                if !ticket.assignee.is_empty() {
+                    lines.push(Line::styled(
+                        format!("  @{}", ticket.assignee),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+            }
+            frame.render_widget(
+                Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(color))
+                        .title(format!("{} ({})", column.status, column.tickets.len())),
+                ),
+                *rect,
+            );
+        }
+    }
+
+    fn draw_charts(&self, frame: &mut Frame, area: Rect, chart: &ChartDTO) {
+        let rects = Layout::vertical([
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+        ])
+        .split(area);
+
+        let x_axis = || {
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, chart.x_max.max(1.0)])
+                .labels(chart.x_labels.clone())
+        };
+        let y_axis = |max: f64| {
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max.max(1.0)])
+                .labels([String::from("0"), format!("{}", max.max(1.0))])
         };
 
-        let paragraph = Paragraph::new(form_text)
-            .block(Block::default().borders(Borders::ALL).title("Edit Screen"));
-        frame.render_widget(paragraph, area);
+        // バーンダウン: 残りポイントの推移
+        let burndown_max = chart.burndown.iter().map(|(_, y)| *y).fold(0.0, f64::max);
+        let burndown = Chart::new(vec![Dataset::default()
+            .name("Remaining points")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&chart.burndown)])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Burndown ({})", chart.window)),
+        )
+        .x_axis(x_axis())
+        .y_axis(y_axis(burndown_max));
+        frame.render_widget(burndown, rects[0]);
+
+        // ベロシティ: 週ごとの解決ポイント
+        let bars: Vec<Bar> = chart
+            .velocity
+            .iter()
+            .map(|(label, value)| {
+                Bar::default()
+                    .label(label.clone().into())
+                    .value(*value)
+                    .style(Style::default().fg(Color::Green))
+            })
+            .collect();
+        let velocity = BarChart::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Velocity (points per week)"),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(5)
+            .bar_gap(1);
+        let middle = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(rects[1]);
+        frame.render_widget(velocity, middle[0]);
+
+        // 担当者ごとの未完了ポイント(負荷の偏りの確認用)
+        let workload_bars: Vec<Bar> = chart
+            .workload
+            .iter()
+            .map(|(assignee, value)| {
+                Bar::default()
+                    .label(assignee.clone().into())
+                    .value(*value)
+                    .style(Style::default().fg(Color::Magenta))
+            })
+            .collect();
+        let workload = BarChart::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Open points by assignee"),
+            )
+            .data(BarGroup::default().bars(&workload_bars))
+            .bar_width(8)
+            .bar_gap(1);
+        frame.render_widget(workload, middle[1]);
+
+        // 累積フロー図: ステータスの分類別のチケット数を積み上げて表示
+        let flow_max = chart.todo_flow.iter().map(|(_, y)| *y).fold(0.0, f64::max);
+        let flow = Chart::new(vec![
+            Dataset::default()
+                .name("To do")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(category_color("todo")))
+                .data(&chart.todo_flow),
+            Dataset::default()
+                .name("In progress")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(category_color("in-progress")))
+                .data(&chart.in_progress_flow),
+            Dataset::default()
+                .name("Done")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(category_color("done")))
+                .data(&chart.done_flow),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Cumulative Flow"),
+        )
+        .x_axis(x_axis())
+        .y_axis(y_axis(flow_max));
+        frame.render_widget(flow, rects[2]);
     }
 }
@@ -1,11 +1,27 @@
 use clap::{Parser, Subcommand};
+use color_eyre::eyre::eyre;
 use color_eyre::Result;
-use dapplication::interactors::terminal_interactor::TerminalInteractor;
+use dapplication::input_ports::cli_input_port::CliInputPort;
+use dapplication::interactors::{
+    api_interactor::ApiInteractor, cli_interactor::CliInteractor,
+    terminal_interactor::TerminalInteractor,
+};
+use dapplication::parsers::query_parser::parse_query;
 use ddomain::repositories::ticket_repository::TicketRepository;
+use ddomain::value_objects::ticket_filter::TicketFilter;
+use dinfrastructure::code_scanner::SourceTreeScanner;
+use dinfrastructure::exporters::exporter_for;
+use dinfrastructure::git_cli::GitCli;
+use dinfrastructure::importers::importer_for;
+use dinfrastructure::merge_driver::merge_files;
+use dinfrastructure::migrations::{upgrade, CURRENT_VERSION};
+use dinfrastructure::storage_backend::{convert, StorageBackend};
 use dinfrastructure::ticket_repository_impl::TicketRepositoryImpl;
+use dinfrastructure::user_identity::current_user_identities;
+use dinfrastructure::workspace_repository::{workspace_files, WorkspaceRepository};
 use dpresentation::{
-    controllers::terminal_controller::TerminalController,
-    presenters::ratatui_presenter::RatatuiPresenter,
+    controllers::{http_controller::HttpController, terminal_controller::TerminalController},
+    presenters::{console_presenter::ConsolePresenter, ratatui_presenter::RatatuiPresenter},
 };
 use std::path::Path;
 
@@ -16,58 +32,440 @@ use std::path::Path;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Storage format of the ticket file (defaults to the file extension)
+    #[arg(long, global = true, value_parser = ["toml", "json", "yaml", "sqlite"])]
+    backend: Option<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    New { file_name: String },
-    Run { file_name: String },
+    New {
+        file_name: String,
+    },
+    /// Open tickets in the terminal UI. Several files or a directory are opened as one workspace
+    Run {
+        #[arg(required = true)]
+        file_names: Vec<String>,
+    },
+    /// List tickets
+    List {
+        file_name: String,
+        /// Only show tickets that have this tag (can be repeated)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only show tickets assigned to this member
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Only show tickets assigned to the current git user or $USER
+        #[arg(long, conflicts_with = "assignee")]
+        mine: bool,
+        /// Only show tickets matching all conditions (e.g. "component=ui severity>=2")
+        #[arg(long)]
+        query: Option<String>,
+    },
+    /// Show open points per assignee
+    Workload {
+        file_name: String,
+    },
+    /// Sum tracked hours per ticket, person or day
+    Timesheet {
+        file_name: String,
+        /// First day of the range (e.g. 2024-05-01, -7d)
+        #[arg(long, default_value = "-6d", allow_hyphen_values = true)]
+        from: String,
+        /// Last day of the range
+        #[arg(long, default_value = "today", allow_hyphen_values = true)]
+        to: String,
+        #[arg(long, default_value = "ticket", value_parser = ["ticket", "person", "day"])]
+        by: String,
+    },
+    /// Print the dependency graph between tickets
+    Graph {
+        file_name: String,
+        #[arg(long, default_value = "dot", value_parser = ["dot", "mermaid"])]
+        format: String,
+    },
+    /// Create the next occurrence of recurring tickets that are due
+    Tick {
+        file_name: String,
+    },
+    /// Render tickets as Markdown, HTML or CSV with the same filters as list
+    Export {
+        file_name: String,
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "checklist", "html", "csv"])]
+        format: String,
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        #[arg(long)]
+        assignee: Option<String>,
+        #[arg(long, conflicts_with = "assignee")]
+        mine: bool,
+        #[arg(long)]
+        query: Option<String>,
+    },
+    /// Append tickets from a CSV, GitHub issues JSON or Jira CSV export
+    Import {
+        file_name: String,
+        source: String,
+        /// Defaults to github for .json sources and csv otherwise
+        #[arg(long, value_parser = ["csv", "github", "jira"])]
+        format: Option<String>,
+        /// Map a field to a CSV column (e.g. "title=Summary"), can be repeated
+        #[arg(long = "map")]
+        mapping: Vec<String>,
+        /// Show what would be imported without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Copy a ticket file into another storage format
+    Convert {
+        source: String,
+        target: String,
+        /// Storage format of the target (defaults to its extension)
+        #[arg(long, value_parser = ["toml", "json", "yaml", "sqlite"])]
+        to: Option<String>,
+        /// Overwrite the target if it exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Rewrite a ticket file in the current format version
+    Migrate {
+        file_name: String,
+    },
+    /// Append a comment to a ticket
+    Comment {
+        file_name: String,
+        id: String,
+        #[arg(short, long)]
+        message: String,
+    },
+    /// Sync TODO, FIXME and HACK comments in a source tree with tickets.
+    /// Files ignored by .gitignore are skipped, and tickets whose comment was removed are resolved
+    Scan {
+        file_name: String,
+        /// Directory to scan
+        #[arg(default_value = ".")]
+        dir: String,
+        /// Show what would change without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Serve the tickets of a file as a JSON API over HTTP.
+    /// Changes need the ticket's ETag in If-Match, so edits made elsewhere in the meantime are not overwritten
+    Serve {
+        file_name: String,
+        /// Address to listen on. Only this machine can connect by default
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        address: String,
+    },
+    /// Three-way merge of a ticket file, used as a git merge driver.
+    ///
+    /// Register it once per repository:
+    ///   git config merge.digger.name "digger ticket merge"
+    ///   git config merge.digger.driver "digger merge-driver %O %A %B"
+    /// and route ticket files to it in .gitattributes:
+    ///   tickets.toml merge=digger
+    MergeDriver {
+        /// Common ancestor (%O)
+        base: String,
+        /// Current branch, receives the result (%A)
+        ours: String,
+        /// Branch being merged (%B)
+        theirs: String,
+    },
+}
+
+// 拡張子が無い場合は保存形式の拡張子(既定は .toml)を補う
+fn resolve_file_path(file_name: String, backend: Option<StorageBackend>) -> String {
+    if Path::new(&file_name).extension().is_some() {
+        file_name
+    } else {
+        format!("{}.{}", file_name, backend.unwrap_or_default().extension())
+    }
+}
+
+// --backend が無い場合は拡張子から保存形式を判断する
+fn resolve_storage(file_name: String, backend: Option<&str>) -> (String, StorageBackend) {
+    let backend = backend.map(|backend| StorageBackend::from(backend.to_string()));
+    let file_path = resolve_file_path(file_name, backend);
+    let backend = backend.unwrap_or_else(|| StorageBackend::from_path(&file_path));
+    (file_path, backend)
+}
+
+fn open_repository(file_name: String, backend: Option<&str>) -> TicketRepositoryImpl {
+    let (file_path, backend) = resolve_storage(file_name, backend);
+    TicketRepositoryImpl::with_backend(file_path, backend)
+}
+
+// git の管理下にあるファイルではコミットとの関連付けとブランチの作成を使える
+fn run_terminal<R: TicketRepository>(repository: R, file_path: &str) -> Result<()> {
+    let presenter = RatatuiPresenter::new();
+
+    // ファイルが存在しない場合、リポジトリ側でファイルを生成
+    repository.ensure_file_exists_with_template()?;
+
+    // TerminalInteractorを使ってTerminalControllerを生成
+    let mut terminal_interactor = TerminalInteractor::new(repository, presenter)?
+        .with_current_user(current_user_identities());
+    if let Some(git) = GitCli::discover(file_path) {
+        terminal_interactor = terminal_interactor.with_version_control(Box::new(git));
+    }
+
+    // エラー処理が成功した場合にのみTerminalControllerを作成
+    let terminal_controller = TerminalController::new(terminal_interactor);
+
+    // ターミナルコントローラの実行
+    terminal_controller.run(ratatui::init())?;
+
+    ratatui::restore();
+    Ok(())
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    let cli = Cli::try_parse()?;
+    let Cli { command, backend } = Cli::try_parse()?;
+    let backend = backend.as_deref();
 
-    match cli.command {
+    match command {
         Commands::New { file_name } => {
-            let file_path = if Path::new(&file_name).extension().is_some() {
-                file_name
-            } else {
-                format!("{}.toml", file_name)
-            };
+            let repository = open_repository(file_name, backend);
+            let file_path = repository.file_path().to_string();
 
-            let repository: Box<dyn TicketRepository> =
-                Box::new(TicketRepositoryImpl::new(file_path.clone()));
+            let repository: Box<dyn TicketRepository> = Box::new(repository);
 
             // ファイルが存在しない場合、リポジトリ側でファイルを生成
             repository.ensure_file_exists_with_template()?;
 
             println!("新しいファイルが生成されました: {}", file_path);
         }
-        Commands::Run { file_name } => {
-            let file_path = if Path::new(&file_name).extension().is_some() {
-                file_name
+        Commands::Run { file_names } => {
+            // 1つのファイルはそのまま、複数のファイルやディレクトリはワークスペースとして開く
+            if file_names.len() == 1 && !Path::new(&file_names[0]).is_dir() {
+                let file_name = file_names.into_iter().next().unwrap_or_default();
+                let repository = open_repository(file_name, backend);
+                let file_path = repository.file_path().to_string();
+                run_terminal(repository, &file_path)?;
             } else {
-                format!("{}.toml", file_name)
-            };
+                let files = workspace_files(&file_names)?;
+                if files.is_empty() {
+                    return Err(eyre!(
+                        "No ticket files were found in {}.",
+                        file_names.join(", ")
+                    ));
+                }
+                let first = files[0].clone();
+                let repositories = files
+                    .into_iter()
+                    .map(|file_name| open_repository(file_name, backend))
+                    .collect();
+                run_terminal(WorkspaceRepository::new(repositories), &first)?;
+            }
+        }
+        Commands::List {
+            file_name,
+            tags,
+            assignee,
+            mine,
+            query,
+        } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
 
-            let repository = TicketRepositoryImpl::new(file_path.clone());
-            let presenter = RatatuiPresenter::new();
+            let conditions =
+                parse_query(query.as_deref().unwrap_or_default()).map_err(|e| eyre!(e))?;
+            CliInteractor::new(repository, ConsolePresenter::new())
+                .with_current_user(current_user_identities())
+                .list(
+                    TicketFilter {
+                        tags,
+                        assignee,
+                        conditions,
+                    },
+                    mine,
+                )?;
+        }
+        Commands::Workload { file_name } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
 
-            // ファイルが存在しない場合、リポジトリ側でファイルを生成
+            CliInteractor::new(repository, ConsolePresenter::new()).workload()?;
+        }
+        Commands::Timesheet {
+            file_name,
+            from,
+            to,
+            by,
+        } => {
+            let repository = open_repository(file_name, backend);
             repository.ensure_file_exists_with_template()?;
 
-            // TerminalInteractorを使ってTerminalControllerを生成
-            let terminal_interactor = TerminalInteractor::new(repository, presenter)?;
+            CliInteractor::new(repository, ConsolePresenter::new()).timesheet(
+                &from,
+                &to,
+                by.into(),
+            )?;
+        }
+        Commands::Graph { file_name, format } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
+
+            CliInteractor::new(repository, ConsolePresenter::new()).graph(format.into())?;
+        }
+        Commands::Tick { file_name } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
+
+            CliInteractor::new(repository, ConsolePresenter::new()).tick()?;
+        }
+        Commands::Export {
+            file_name,
+            format,
+            tags,
+            assignee,
+            mine,
+            query,
+        } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
+
+            // HTML の見出しには拡張子を除いたファイル名を使う
+            let title = Path::new(repository.file_path())
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let conditions =
+                parse_query(query.as_deref().unwrap_or_default()).map_err(|e| eyre!(e))?;
+            CliInteractor::new(repository, ConsolePresenter::new())
+                .with_current_user(current_user_identities())
+                .export(
+                    TicketFilter {
+                        tags,
+                        assignee,
+                        conditions,
+                    },
+                    mine,
+                    exporter_for(format.into(), &title).as_ref(),
+                )?;
+        }
+        Commands::Import {
+            file_name,
+            source,
+            format,
+            mapping,
+            dry_run,
+        } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
+
+            let format = format.unwrap_or_else(|| {
+                match Path::new(&source).extension().and_then(|ext| ext.to_str()) {
+                    Some("json") => "github".to_string(),
+                    _ => "csv".to_string(),
+                }
+            });
+            let importer = importer_for(format.into(), source, &mapping)?;
+            CliInteractor::new(repository, ConsolePresenter::new())
+                .import(importer.as_ref(), dry_run)?;
+        }
+        Commands::Convert {
+            source,
+            target,
+            to,
+            force,
+        } => {
+            let (source_path, source_backend) = resolve_storage(source, backend);
+            let (target_path, target_backend) = resolve_storage(target, to.as_deref());
+            let source_store = source_backend.open(source_path.clone());
+            let target_store = target_backend.open(target_path.clone());
+            if !source_store.exists() {
+                return Err(eyre!("The file at {} was not found.", source_path));
+            }
+            if target_store.exists() && !force {
+                return Err(eyre!(
+                    "{} already exists (use --force to overwrite)",
+                    target_path
+                ));
+            }
+
+            let count = convert(source_store.as_ref(), target_store.as_ref())?;
+            println!(
+                "Converted {} tickets from {} to {}",
+                count, source_path, target_path
+            );
+        }
+        Commands::Migrate { file_name } => {
+            let (file_path, backend) = resolve_storage(file_name, backend);
+            let store = backend.open(file_path.clone());
+            if !store.exists() {
+                return Err(eyre!("The file at {} was not found.", file_path));
+            }
+
+            let version = upgrade(store.as_ref())?;
+            if version < CURRENT_VERSION {
+                println!(
+                    "Migrated {} from version {} to {}",
+                    file_path, version, CURRENT_VERSION
+                );
+            } else {
+                println!("{} is already at version {}", file_path, CURRENT_VERSION);
+            }
+        }
+        Commands::Comment {
+            file_name,
+            id,
+            message,
+        } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
 
-            // エラー処理が成功した場合にのみTerminalControllerを作成
-            let terminal_controller = TerminalController::new(terminal_interactor);
+            CliInteractor::new(repository, ConsolePresenter::new())
+                .with_current_user(current_user_identities())
+                .comment(&id, &message)?;
+        }
+        Commands::Scan {
+            file_name,
+            dir,
+            dry_run,
+        } => {
+            if !Path::new(&dir).is_dir() {
+                return Err(eyre!("The directory at {} was not found.", dir));
+            }
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
+            // 記録するパスはチケットファイルのディレクトリを基準にする
+            let base = Path::new(repository.file_path())
+                .parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .filter(|parent| !parent.is_empty())
+                .unwrap_or_else(|| ".".to_string());
+            let scanner = SourceTreeScanner::new(dir).relative_to(base);
 
-            // ターミナルコントローラの実行
-            terminal_controller.run(ratatui::init())?;
+            CliInteractor::new(repository, ConsolePresenter::new()).scan(&scanner, dry_run)?;
+        }
+        Commands::Serve { file_name, address } => {
+            let repository = open_repository(file_name, backend);
+            repository.ensure_file_exists_with_template()?;
+            let file_path = repository.file_path().to_string();
 
-            ratatui::restore();
+            let interactor =
+                ApiInteractor::new(repository).with_current_user(current_user_identities());
+            println!("Serving {} on http://{}", file_path, address);
+            HttpController::new(interactor).run(&address)?;
+        }
+        Commands::MergeDriver { base, ours, theirs } => {
+            let outcome = merge_files(&base, &ours, &theirs)?;
+            for note in &outcome.notes {
+                eprintln!("{}", note);
+            }
+            // 競合が残る場合は 0 以外で終了し、git に競合として扱わせる
+            if outcome.conflicts > 0 {
+                eprintln!(
+                    "{} field conflicts are left to resolve by hand",
+                    outcome.conflicts
+                );
+                std::process::exit(1);
+            }
         }
     }
 